@@ -158,7 +158,6 @@ struct Sinebow {
 impl Sinebow {
     fn draw(&self, canvas: &mut Canvas) {
         let color = Self::next_color(self.pos);
-        let offset = canvas.area().left_top();
         let w = canvas.area().width();
 
         for (pos, c) in ('a'..='z')
@@ -167,7 +166,7 @@ impl Sinebow {
             .cycle()
             .take(canvas.area().area() as usize)
             .enumerate()
-            .map(|(i, c)| (pos2(i as u16 % w, i as u16 / w) + offset, c))
+            .map(|(i, c)| (pos2(i as u16 % w, i as u16 / w), c))
         {
             canvas.put(pos, Cell::new(c).fg(color))
         }
@@ -179,7 +178,7 @@ impl Sinebow {
 
     fn next_color(n: f32) -> Rgb {
         let h = n * ((1.0 + 5.0_f32.sqrt()) / 2.0);
-        let h = (h + 0.5) * -1.0;
+        let h = -(h + 0.5);
         let r = (PI * h).sin();
         let g = (PI * (h + 0.3)).sin();
         let b = (PI * (h + 0.6)).sin();
@@ -1,5 +1,7 @@
 use std::sync::{Arc, Mutex};
 
+use shuten_core::geom::Align2;
+
 use crate::terminal::Timer;
 
 /// Configuration for a [`Terminal`](crate::Terminal)
@@ -13,6 +15,7 @@ use crate::terminal::Timer;
 /// | [use alt screen](Self::use_alt_screen) | `true` |
 /// | [ctrl z switches](Self::ctrl_z_switches) | `false` |
 /// | [timer](Self::reactive_timer)          | `reactive` |
+/// | [resize anchor](Self::resize_anchor)   | `left-top` |
 #[derive(Copy, Clone, Debug)]
 #[non_exhaustive]
 pub struct Config {
@@ -22,6 +25,7 @@ pub struct Config {
     pub(crate) ctrl_z_switches: bool,
     pub(crate) use_alt_screen: bool,
     pub(crate) timer: Timer,
+    pub(crate) resize_anchor: Align2,
 }
 
 impl Default for Config {
@@ -33,6 +37,7 @@ impl Default for Config {
             ctrl_z_switches: false,
             use_alt_screen: true,
             timer: Timer::default(),
+            resize_anchor: Align2::LEFT_TOP,
         }
     }
 }
@@ -71,6 +76,12 @@ impl Config {
         self
     }
 
+    /// Where should the existing contents be anchored when the terminal is resized?
+    pub const fn resize_anchor(mut self, resize_anchor: Align2) -> Self {
+        self.resize_anchor = resize_anchor;
+        self
+    }
+
     /// Should it use a fixed timer?
     ///
     /// This takes in a desired framerate (`fps`) and produces a [`Event::Blend`](crate::event::Event::Blend) inbetween frames so you can interpolate your application state
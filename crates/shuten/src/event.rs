@@ -1,5 +1,5 @@
 //! Events produced by a [`Terminal`](crate::Terminal)
-use shuten_core::geom::{Pos2, Rect};
+use shuten_core::geom::{Pos2, Rect, ResizeDelta};
 
 mod key;
 pub use key::Key;
@@ -16,8 +16,8 @@ pub use modifiers::Modifiers;
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[non_exhaustive]
 pub enum Event {
-    /// A resize event happened, giving you the new screen [`Rect`]
-    Invalidate(Rect),
+    /// A resize event happened, giving you the old and new screen [`Rect`]s
+    Invalidate(Resize),
     /// A mouse event happened, giving you the event and any [`Modifiers`]
     Mouse(MouseEvent, Modifiers),
     /// A keyboard event happened, giving you the event and any [`Modifiers`]
@@ -58,6 +58,52 @@ impl Event {
     }
 }
 
+/// The old and new screen [`Rect`]s of an [`Event::Invalidate`]
+///
+/// ```rust
+/// use shuten::{event::Resize, geom::{rect, vec2}};
+/// let resize = Resize {
+///     old: rect(vec2(10, 10)),
+///     new: rect(vec2(15, 5)),
+/// };
+///
+/// let mut rect = resize.old;
+/// resize.grown().for_each(|delta| rect += delta);
+/// resize.shrunk().for_each(|delta| rect -= delta);
+/// assert_eq!(rect, resize.new);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Resize {
+    /// The screen before the resize
+    pub old: Rect,
+    /// The screen after the resize
+    pub new: Rect,
+}
+
+impl Resize {
+    /// Did the size of the screen change?
+    pub fn size_changed(&self) -> bool {
+        self.old.size() != self.new.size()
+    }
+
+    /// The [`ResizeDelta`]s that the screen grew by, these should be added to the old [`Rect`]
+    pub fn grown(&self) -> impl Iterator<Item = ResizeDelta> {
+        let (old, new) = (self.old.size(), self.new.size());
+        let right = (new.x > old.x).then(|| ResizeDelta::Right(new.x - old.x));
+        let down = (new.y > old.y).then(|| ResizeDelta::Down(new.y - old.y));
+        right.into_iter().chain(down)
+    }
+
+    /// The [`ResizeDelta`]s that the screen shrunk by, these should be subtracted from the old [`Rect`]
+    pub fn shrunk(&self) -> impl Iterator<Item = ResizeDelta> {
+        let (old, new) = (self.old.size(), self.new.size());
+        let right = (new.x < old.x).then(|| ResizeDelta::Right(old.x - new.x));
+        let down = (new.y < old.y).then(|| ResizeDelta::Down(old.y - new.y));
+        right.into_iter().chain(down)
+    }
+}
+
 /// Create [`EventKind`] filters for use with [`Terminal::wait_for_event`](crate::Terminal::wait_for_event)
 impl Event {
     pub const fn invalidate() -> EventKind {
@@ -87,7 +133,10 @@ pub struct EventKind(pub(crate) std::mem::Discriminant<Event>);
 
 impl EventKind {
     pub const fn invalidate() -> Self {
-        Self(std::mem::discriminant(&Event::Invalidate(Rect::ZERO)))
+        Self(std::mem::discriminant(&Event::Invalidate(Resize {
+            old: Rect::ZERO,
+            new: Rect::ZERO,
+        })))
     }
 
     pub const fn mouse() -> Self {
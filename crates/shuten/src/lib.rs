@@ -9,3 +9,9 @@ pub use shuten_core::*;
 
 mod config;
 pub use config::{Config, ShareableConfig};
+
+// the terminal can be moved to another thread
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Terminal>();
+};
@@ -8,17 +8,16 @@ use std::{
 };
 
 use crate::{
-    event::{Event, EventKind, Key, Modifiers, MouseState},
+    event::{Event, EventKind, Key, Modifiers, MouseState, Resize},
     Config, ShareableConfig,
 };
 
 use shuten_core::{
-    geom::{self, Rect, Vec2},
+    geom::{self, Pos2, Rect, Vec2},
     renderer::{
         metrics::{FrameStats, MetricsRenderer},
         Renderer, TermRenderer,
     },
-    style::Color,
     Canvas, Context, Surface,
 };
 
@@ -98,7 +97,7 @@ impl Terminal {
     }
 
     /// Create a [`Canvas`] to draw to
-    pub fn canvas(&mut self) -> Canvas {
+    pub fn canvas(&mut self) -> Canvas<'_> {
         self.context.canvas()
     }
 
@@ -319,7 +318,9 @@ impl Terminal {
         // this is an average of every cell set to an rgb color, rounded up
         let capacity = (size.x as usize * size.y as usize).next_power_of_two();
         self.out = BufWriter::with_capacity(capacity, out);
-        self.context.resize(size)
+
+        let anchor = self.config.get(|config| config.resize_anchor);
+        self.context.resize_anchored(size, anchor)
     }
 
     fn read_event(&mut self) -> std::io::Result<Option<Event>> {
@@ -334,8 +335,8 @@ impl Terminal {
                 config,
             )
         })?;
-        if let Some(Event::Invalidate(rect)) = ev {
-            self.resize(rect.size());
+        if let Some(Event::Invalidate(Resize { new, .. })) = ev {
+            self.resize(new.size());
         }
 
         if switch {
@@ -394,10 +395,9 @@ impl Terminal {
             }
 
             E::Resize(cols, rows) => {
-                ctx.resize(geom::vec2(cols, rows));
-                let rect = ctx.rect();
-                ctx.canvas().fill(Color::Reset);
-                return Ok(Some(Event::Invalidate(rect)));
+                let old = ctx.rect();
+                let new = Rect::from_min_size(Pos2::ZERO, geom::vec2(cols, rows));
+                return Ok(Some(Event::Invalidate(Resize { old, new })));
             }
             _ => return Ok(None),
         };
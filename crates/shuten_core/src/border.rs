@@ -0,0 +1,377 @@
+//! Borders drawn with box-drawing characters
+//!
+//! When borders meet or cross, they are merged into the correct junction (e.g. `┼`, `├`, `╬`)
+//!
+//! ```rust
+//! use shuten_core::border::{Border, LineStyle};
+//! use shuten_core::geom::{pos2, rect, vec2};
+//! use shuten_core::Context;
+//!
+//! let mut context = Context::new(rect(vec2(10, 5)));
+//! let mut canvas = context.canvas();
+//!
+//! // the two panes share a column, so it becomes a junction
+//! let (left, right) = canvas.area().split_horizontal(5);
+//! canvas.border(left, Border::new(LineStyle::Single));
+//! canvas.border(right, Border::new(LineStyle::Single));
+//!
+//! assert_eq!(context.surface()[pos2(4, 0)].char, '┬');
+//! assert_eq!(context.surface()[pos2(4, 4)].char, '┴');
+//! ```
+use crate::{
+    style::{Attribute, Color},
+    Cell,
+};
+
+/// The style of line used to draw a [`Border`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum LineStyle {
+    /// Single lines, e.g. `┌─┐`
+    #[default]
+    Single,
+    /// Double lines, e.g. `╔═╗`
+    Double,
+    /// Single lines with rounded corners, e.g. `╭─╮`
+    Rounded,
+    /// Heavy lines, e.g. `┏━┓`
+    Heavy,
+    /// Dashed lines, e.g. `┌┄┐`
+    Dashed,
+    /// ASCII lines, e.g. `+-+`
+    ///
+    /// This can be used as a fallback for terminals without box-drawing characters
+    ///
+    /// These only merge with other ASCII borders
+    Ascii,
+}
+
+impl LineStyle {
+    const fn weight(self) -> Weight {
+        match self {
+            Self::Single | Self::Rounded | Self::Dashed | Self::Ascii => Weight::Light,
+            Self::Double => Weight::Double,
+            Self::Heavy => Weight::Heavy,
+        }
+    }
+}
+
+/// A border, drawn with [`Canvas::border`](crate::Canvas::border) and [`Canvas::border_line`](crate::Canvas::border_line)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Border {
+    pub(crate) line: LineStyle,
+    pub(crate) style: Cell,
+}
+
+impl Default for Border {
+    fn default() -> Self {
+        Self::new(LineStyle::default())
+    }
+}
+
+impl Border {
+    /// Create a new border with this [`LineStyle`]
+    pub const fn new(line: LineStyle) -> Self {
+        Self {
+            line,
+            style: Cell::new(' '),
+        }
+    }
+
+    /// Set the [`LineStyle`] of the border
+    pub const fn line(mut self, line: LineStyle) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Set the colors and attribute of the border from this [`Cell`]
+    ///
+    /// The character of the [`Cell`] is ignored
+    pub const fn style(mut self, style: Cell) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the foreground of the border
+    pub fn fg(mut self, fg: impl Into<Color>) -> Self {
+        self.style = self.style.fg(fg);
+        self
+    }
+
+    /// Set the background of the border
+    pub fn bg(mut self, bg: impl Into<Color>) -> Self {
+        self.style = self.style.bg(bg);
+        self
+    }
+
+    /// Set the attribute of the border
+    ///
+    /// If `none` is provided, then the attribute is reset
+    pub fn attr(mut self, attr: impl Into<Option<Attribute>>) -> Self {
+        self.style = self.style.attr(attr);
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum Weight {
+    #[default]
+    None,
+    Light,
+    Heavy,
+    Double,
+}
+
+/// The arms of a box-drawing character, in `up`, `right`, `down`, `left` order
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Arms([Weight; 4]);
+
+impl Arms {
+    /// Arms for a border using this [`LineStyle`]
+    pub(crate) fn new(line: LineStyle, up: bool, right: bool, down: bool, left: bool) -> Self {
+        let weight = |arm: bool| if arm { line.weight() } else { Weight::None };
+        Self([weight(up), weight(right), weight(down), weight(left)])
+    }
+
+    const fn parse(code: &[u8; 4]) -> Self {
+        const fn weight(code: u8) -> Weight {
+            match code {
+                b'l' => Weight::Light,
+                b'h' => Weight::Heavy,
+                b'd' => Weight::Double,
+                _ => Weight::None,
+            }
+        }
+        Self([
+            weight(code[0]),
+            weight(code[1]),
+            weight(code[2]),
+            weight(code[3]),
+        ])
+    }
+
+    fn decode(char: char, line: LineStyle) -> Option<Self> {
+        if line == LineStyle::Ascii {
+            return match char {
+                '-' => Some(Self::parse(b".l.l")),
+                '|' => Some(Self::parse(b"l.l.")),
+                '+' => Some(Self::parse(b"llll")),
+                _ => None,
+            };
+        }
+
+        BOX_DRAWING
+            .iter()
+            .chain(DECODE_ONLY)
+            .find_map(|&(c, code)| (c == char).then(|| Self::parse(code)))
+    }
+
+    fn encode(self, line: LineStyle) -> char {
+        if self == Self::default() {
+            return ' ';
+        }
+
+        let [up, right, down, left] = self.0.map(|arm| arm != Weight::None);
+        if line == LineStyle::Ascii {
+            return match (up || down, right || left) {
+                (false, true) => '-',
+                (true, false) => '|',
+                _ => '+',
+            };
+        }
+
+        let find = |arms: Self| {
+            BOX_DRAWING
+                .iter()
+                .find_map(|&(c, code)| (Self::parse(code) == arms).then_some(c))
+        };
+
+        let light = self.with_weight(Weight::Light);
+        if line == LineStyle::Rounded && self == light {
+            match (up, right, down, left) {
+                (false, true, true, false) => return '╭',
+                (false, false, true, true) => return '╮',
+                (true, false, false, true) => return '╯',
+                (true, true, false, false) => return '╰',
+                _ => {}
+            }
+        }
+
+        if line == LineStyle::Dashed && self == light {
+            match (up, right, down, left) {
+                (false, true, false, true) => return '┄',
+                (true, false, true, false) => return '┆',
+                _ => {}
+            }
+        }
+
+        // not every combination of weights has a character,
+        // so try to use the weight of the line being drawn, then fallback to a light line
+        find(self)
+            .or_else(|| find(self.with_weight(line.weight())))
+            .or_else(|| find(light))
+            .unwrap_or(' ')
+    }
+
+    fn with_weight(self, weight: Weight) -> Self {
+        Self(self.0.map(|arm| match arm {
+            Weight::None => Weight::None,
+            _ => weight,
+        }))
+    }
+
+    fn merge(self, other: Self) -> Self {
+        let mut arms = self.0;
+        for (arm, new) in arms.iter_mut().zip(other.0) {
+            if new != Weight::None {
+                *arm = new;
+            }
+        }
+        Self(arms)
+    }
+}
+
+/// Merge these arms into an existing character, producing a new character
+pub(crate) fn merge(existing: char, arms: Arms, line: LineStyle) -> char {
+    Arms::decode(existing, line)
+        .map_or(arms, |old| old.merge(arms))
+        .encode(line)
+}
+
+/// Box-drawing characters and their arms, in `up`, `right`, `down`, `left` order
+///
+/// - `.` is no arm
+/// - `l` is a light arm
+/// - `h` is a heavy arm
+/// - `d` is a double arm
+const BOX_DRAWING: &[(char, &[u8; 4])] = &[
+    ('─', b".l.l"),
+    ('━', b".h.h"),
+    ('│', b"l.l."),
+    ('┃', b"h.h."),
+    ('┌', b".ll."),
+    ('┍', b".hl."),
+    ('┎', b".lh."),
+    ('┏', b".hh."),
+    ('┐', b"..ll"),
+    ('┑', b"..lh"),
+    ('┒', b"..hl"),
+    ('┓', b"..hh"),
+    ('└', b"ll.."),
+    ('┕', b"lh.."),
+    ('┖', b"hl.."),
+    ('┗', b"hh.."),
+    ('┘', b"l..l"),
+    ('┙', b"l..h"),
+    ('┚', b"h..l"),
+    ('┛', b"h..h"),
+    ('├', b"lll."),
+    ('┝', b"lhl."),
+    ('┞', b"hll."),
+    ('┟', b"llh."),
+    ('┠', b"hlh."),
+    ('┡', b"hhl."),
+    ('┢', b"lhh."),
+    ('┣', b"hhh."),
+    ('┤', b"l.ll"),
+    ('┥', b"l.lh"),
+    ('┦', b"h.ll"),
+    ('┧', b"l.hl"),
+    ('┨', b"h.hl"),
+    ('┩', b"h.lh"),
+    ('┪', b"l.hh"),
+    ('┫', b"h.hh"),
+    ('┬', b".lll"),
+    ('┭', b".llh"),
+    ('┮', b".hll"),
+    ('┯', b".hlh"),
+    ('┰', b".lhl"),
+    ('┱', b".lhh"),
+    ('┲', b".hhl"),
+    ('┳', b".hhh"),
+    ('┴', b"ll.l"),
+    ('┵', b"ll.h"),
+    ('┶', b"lh.l"),
+    ('┷', b"lh.h"),
+    ('┸', b"hl.l"),
+    ('┹', b"hl.h"),
+    ('┺', b"hh.l"),
+    ('┻', b"hh.h"),
+    ('┼', b"llll"),
+    ('┽', b"lllh"),
+    ('┾', b"lhll"),
+    ('┿', b"lhlh"),
+    ('╀', b"hlll"),
+    ('╁', b"llhl"),
+    ('╂', b"hlhl"),
+    ('╃', b"hllh"),
+    ('╄', b"hhll"),
+    ('╅', b"llhh"),
+    ('╆', b"lhhl"),
+    ('╇', b"hhlh"),
+    ('╈', b"lhhh"),
+    ('╉', b"hlhh"),
+    ('╊', b"hhhl"),
+    ('╋', b"hhhh"),
+    ('═', b".d.d"),
+    ('║', b"d.d."),
+    ('╒', b".dl."),
+    ('╓', b".ld."),
+    ('╔', b".dd."),
+    ('╕', b"..ld"),
+    ('╖', b"..dl"),
+    ('╗', b"..dd"),
+    ('╘', b"ld.."),
+    ('╙', b"dl.."),
+    ('╚', b"dd.."),
+    ('╛', b"l..d"),
+    ('╜', b"d..l"),
+    ('╝', b"d..d"),
+    ('╞', b"ldl."),
+    ('╟', b"dld."),
+    ('╠', b"ddd."),
+    ('╡', b"l.ld"),
+    ('╢', b"d.dl"),
+    ('╣', b"d.dd"),
+    ('╤', b".dld"),
+    ('╥', b".ldl"),
+    ('╦', b".ddd"),
+    ('╧', b"ld.d"),
+    ('╨', b"dl.l"),
+    ('╩', b"dd.d"),
+    ('╪', b"ldld"),
+    ('╫', b"dldl"),
+    ('╬', b"dddd"),
+    ('╴', b"...l"),
+    ('╵', b"l..."),
+    ('╶', b".l.."),
+    ('╷', b"..l."),
+    ('╸', b"...h"),
+    ('╹', b"h..."),
+    ('╺', b".h.."),
+    ('╻', b"..h."),
+    ('╼', b".h.l"),
+    ('╽', b"l.h."),
+    ('╾', b".l.h"),
+    ('╿', b"h.l."),
+];
+
+/// Characters that are only decoded, they are produced by a specific [`LineStyle`]
+const DECODE_ONLY: &[(char, &[u8; 4])] = &[
+    ('╭', b".ll."),
+    ('╮', b"..ll"),
+    ('╯', b"l..l"),
+    ('╰', b"ll.."),
+    ('┄', b".l.l"),
+    ('┅', b".h.h"),
+    ('┆', b"l.l."),
+    ('┇', b"h.h."),
+    ('┈', b".l.l"),
+    ('┉', b".h.h"),
+    ('┊', b"l.l."),
+    ('┋', b"h.h."),
+    ('╌', b".l.l"),
+    ('╍', b".h.h"),
+    ('╎', b"l.l."),
+    ('╏', b"h.h."),
+];
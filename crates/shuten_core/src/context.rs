@@ -1,18 +1,45 @@
+use std::{borrow::Cow, ops::Range};
+
 use crate::{
-    geom::{Pos2, Rect, Vec2},
+    geom::{pos2, vec2, Align2, Pos2, Rect, Vec2},
     renderer::Renderer,
-    style::Color,
-    surface::{CellAttr, Surface},
-    Canvas,
+    style::{Attribute, Color, Underline},
+    surface::{CellAttr, Hyperlink, Link, Surface},
+    Canvas, Cell,
 };
 
 /// Context allows efficient double buffering
 ///
 /// You'd generally use this type over a raw [`Surface`]
+///
+/// It can also have layers above its [`Surface`], see [`Context::layer`]
 pub struct Context {
     pub(crate) rect: Rect,
     pub(crate) front: Surface,
     pub(crate) back: Surface,
+    layers: Vec<Layer>,
+    scrolls: Vec<(Range<u16>, i32)>,
+    invalidated: bool,
+}
+
+/// A [`Surface`] composited above the [`Context`]'s surface
+#[derive(Debug)]
+struct Layer {
+    z: u16,
+    visible: bool,
+    surface: Surface,
+}
+
+impl Layer {
+    fn new(z: u16, size: Vec2) -> Self {
+        let mut surface = Surface::new(size);
+        surface.fill(Cell::TRANSPARENT);
+        Self {
+            z,
+            visible: true,
+            surface,
+        }
+    }
 }
 
 impl Context {
@@ -22,6 +49,9 @@ impl Context {
             rect,
             front: Surface::new(rect.size()),
             back: Surface::new(rect.size()),
+            layers: Vec::new(),
+            scrolls: Vec::new(),
+            invalidated: false,
         }
     }
 
@@ -37,8 +67,8 @@ impl Context {
     /// They are cheap to make, but you cannot copy nor clone them.
     ///
     /// If you need to share them, do it via `&mut` borrow
-    pub fn canvas(&mut self) -> Canvas {
-        Canvas::new(self.rect, &mut self.back)
+    pub fn canvas(&mut self) -> Canvas<'_> {
+        self.back.canvas()
     }
 
     /// Get the current [`Surface`] that'll be drawn upon
@@ -46,12 +76,181 @@ impl Context {
         &mut self.back
     }
 
+    /// Scroll these rows of the [`Surface`] by an amount
+    ///
+    /// - Positive amounts scroll the rows up, blank rows are exposed at the bottom
+    /// - Negative amounts scroll the rows down, blank rows are exposed at the top
+    ///
+    /// The [`Surface`] is scrolled immediately, so only the exposed rows need to be drawn.
+    ///
+    /// When the frame ends, the terminal is scrolled with a scroll region, so only the exposed rows are written.
+    /// If the [`Renderer`] can't [scroll](Renderer::supports_scroll), or only a single row is scrolled, the scrolled rows are written instead
+    ///
+    /// ***NOTE*** Terminals scroll entire rows, so this should only be used when the [`Context`] spans the width of the terminal
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2};
+    /// use shuten_core::renderer::help::explain_frame;
+    /// use shuten_core::{Cell, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(10, 5)));
+    /// context.canvas().put(pos2(0, 4), Cell::new('a'));
+    /// context.end_frame(&mut shuten_core::renderer::NullRenderer).unwrap();
+    ///
+    /// context.scroll(0..5, 1);
+    /// assert_eq!(context.surface()[pos2(0, 3)].char, 'a');
+    ///
+    /// let explain = explain_frame(&mut context, |mut canvas| {
+    ///     canvas.put(pos2(0, 4), Cell::new('b'));
+    /// })
+    /// .unwrap();
+    /// assert!(explain.contains("scroll up 1"));
+    /// ```
+    pub fn scroll(&mut self, rows: Range<u16>, amount: i32) {
+        let rows = rows.start..rows.end.min(self.back.size().y);
+        if rows.is_empty() || amount == 0 {
+            return;
+        }
+        self.back.scroll(rows.clone(), amount);
+        // a scroll region needs at least two rows, a single row is just drawn again
+        if rows.len() >= 2 {
+            self.scrolls.push((rows, amount));
+        }
+    }
+
+    /// Create a [`Canvas`] for the layer at this `z` index, creating the layer if it doesn't exist
+    ///
+    /// Layers are composited above the [`Context`]'s surface, from the lowest `z` index to the highest.
+    ///
+    /// Layers start out [transparent](Cell::TRANSPARENT), and keep their contents between frames. See [`Context::clear_layer`]
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2};
+    /// use shuten_core::{renderer::NullRenderer, Cell, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(10, 5)));
+    /// context.canvas().put(pos2(0, 0), Cell::new('a'));
+    /// context.layer(1).put(pos2(1, 0), Cell::new('b'));
+    ///
+    /// context.hide_layer(1);
+    /// assert!(!context.is_layer_visible(1));
+    /// context.end_frame(&mut NullRenderer).unwrap();
+    /// ```
+    pub fn layer(&mut self, z: u16) -> Canvas<'_> {
+        let index = match self.layers.binary_search_by_key(&z, |layer| layer.z) {
+            Ok(index) => index,
+            Err(index) => {
+                self.layers.insert(index, Layer::new(z, self.rect.size()));
+                index
+            }
+        };
+        self.layers[index].surface.canvas()
+    }
+
+    /// Get the [`Surface`] for the layer at this `z` index, if it exists
+    pub fn layer_surface(&mut self, z: u16) -> Option<&mut Surface> {
+        self.find_layer(z).map(|layer| &mut layer.surface)
+    }
+
+    /// Show the layer at this `z` index
+    pub fn show_layer(&mut self, z: u16) {
+        self.set_layer_visible(z, true)
+    }
+
+    /// Hide the layer at this `z` index
+    ///
+    /// The layer keeps its contents, so it can be shown again without repainting it
+    pub fn hide_layer(&mut self, z: u16) {
+        self.set_layer_visible(z, false)
+    }
+
+    fn set_layer_visible(&mut self, z: u16, visible: bool) {
+        let Some(layer) = self.find_layer(z) else {
+            return;
+        };
+        if std::mem::replace(&mut layer.visible, visible) != visible {
+            // whatever was beneath the layer has to be compared again
+            self.mark_dirty();
+        }
+    }
+
+    /// Is the layer at this `z` index visible?
+    ///
+    /// This is `false` if the layer doesn't exist
+    pub fn is_layer_visible(&self, z: u16) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.z == z && layer.visible)
+    }
+
+    /// Make the layer at this `z` index entirely [transparent](Cell::TRANSPARENT)
+    pub fn clear_layer(&mut self, z: u16) {
+        if let Some(layer) = self.find_layer(z) {
+            layer.surface.fill(Cell::TRANSPARENT);
+            self.mark_dirty();
+        }
+    }
+
+    /// Remove the layer at this `z` index
+    pub fn remove_layer(&mut self, z: u16) {
+        let len = self.layers.len();
+        self.layers.retain(|layer| layer.z != z);
+        if self.layers.len() != len {
+            self.mark_dirty();
+        }
+    }
+
+    /// Mark the entire [`Surface`] as dirty, so every cell is compared when the frame ends
+    ///
+    /// This is useful if the terminal was changed by something else
+    pub fn mark_dirty(&mut self) {
+        self.back.mark_dirty(self.back.rect())
+    }
+
+    /// Mark the entire [`Surface`] as clean, so only cells written after this are compared when the frame ends
+    ///
+    /// Cells are marked as dirty when they are written to, and marked as clean when the frame ends
+    pub fn mark_clean(&mut self) {
+        self.back.mark_clean();
+        for layer in &mut self.layers {
+            layer.surface.mark_clean();
+        }
+    }
+
+    /// Get the [`Surface`] that would be drawn if the frame ended now
+    ///
+    /// This is the current [`Surface`], with any visible layers composited over it
+    pub fn frame(&self) -> Cow<'_, Surface> {
+        self.composite_layers()
+            .map_or(Cow::Borrowed(&self.back), Cow::Owned)
+    }
+
+    fn find_layer(&mut self, z: u16) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|layer| layer.z == z)
+    }
+
     /// Resize this [`Context`] using a provided [size](Vec2)
-    #[cfg_attr(feature = "profiling", profiling::function)]
+    ///
+    /// This keeps the overlapping cells, anchored to the top-left. See [`Context::resize_anchored`]
     pub fn resize(&mut self, size: Vec2) {
+        self.resize_anchored(size, Align2::LEFT_TOP)
+    }
+
+    /// Resize this [`Context`] using a provided [size](Vec2), keeping the overlapping cells aligned to the `anchor`
+    ///
+    /// Any layers are resized the same way.
+    ///
+    /// The screen is cleared and entirely redrawn when the frame ends, as the terminal may have changed its contents
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub fn resize_anchored(&mut self, size: Vec2, anchor: Align2) {
+        self.scrolls.clear();
         self.front.resize(size);
-        self.back.resize(size);
+        self.back.resize_anchored(size, anchor);
+        for layer in &mut self.layers {
+            layer.surface.resize_with(size, anchor, Cell::TRANSPARENT);
+        }
         self.rect = Rect::from_min_size(Pos2::ZERO, size);
+        self.invalidated = true;
     }
 
     /// End frame diffs the internal [`Surface`]s and writes a minimal amount of commands to the provided [`Renderer`].
@@ -65,13 +264,68 @@ impl Context {
     ///     - This is generally what you'd use to write things to a terminal
     /// - [`NullRenderer`](crate::renderer::NullRenderer)
     ///     - This does nothing
+    ///
+    /// Any visible layers are composited over the [`Surface`] before it is diffed
+    ///
+    /// Any [scrolls](Context::scroll) are done before the diff
+    ///
+    /// Hyperlinks are started and ended only when the [link](Cell::link) of the written cells changes
+    ///
+    /// If the [`Context`] was [resized](Context::resize), the screen is cleared first
     #[cfg_attr(feature = "profiling", profiling::function)]
     pub fn end_frame(&mut self, out: &mut impl Renderer) -> std::io::Result<()> {
         let mut state = CursorState::default();
+        let can_unset = out.supports_unset_attr();
         let mut seen = false;
         let mut wrote_reset = false;
 
-        for (pos, change) in self.front.diff(&self.back) {
+        if std::mem::take(&mut self.invalidated) {
+            out.begin()?;
+            seen = true;
+
+            out.reset_bg()?;
+            out.clear_screen()?;
+            // the front now matches the cleared screen, so every other cell is redrawn
+            self.front.fill(Cell::RESET);
+        }
+
+        if !out.supports_scroll() {
+            // the terminal wasn't scrolled, so the scrolled rows are written again
+            let width = self.back.size().x;
+            for (rows, _) in self.scrolls.drain(..) {
+                let rect =
+                    Rect::from_min_size(pos2(0, rows.start), vec2(width, rows.end - rows.start));
+                self.back.mark_dirty(rect);
+            }
+        }
+
+        if !self.scrolls.is_empty() {
+            if !seen {
+                out.begin()?;
+                seen = true;
+            }
+
+            // the exposed rows are filled with the current background
+            out.reset_bg()?;
+
+            let top = self.rect.top();
+            for (rows, amount) in self.scrolls.drain(..) {
+                out.set_scroll_region(top + rows.start..top + rows.end)?;
+                let lines = amount.unsigned_abs().min(u16::MAX as u32) as u16;
+                match amount.is_positive() {
+                    true => out.scroll_up(lines)?,
+                    false => out.scroll_down(lines)?,
+                }
+                // keep the front in sync with the terminal, so only the exposed rows are different
+                self.front.scroll(rows, amount);
+            }
+            out.reset_scroll_region()?;
+        }
+
+        let composited = self.composite_layers();
+        let back = composited.as_ref().unwrap_or(&self.back);
+
+        for (pos, change) in self.front.diff(back) {
             if !seen {
                 out.begin()?;
                 seen = true;
@@ -79,46 +333,105 @@ impl Context {
 
             let pos = self.rect.left_top() + pos;
 
-            if state.maybe_move(pos) {
+            if state.maybe_move(pos, change.width()) {
                 out.move_to(pos)?;
             }
 
-            match state.maybe_attr(change.attr) {
-                Some(CellAttr::Attr(attr)) => {
-                    wrote_reset = false;
-                    out.set_attr(attr)?
+            match state.maybe_attr(change.attr, can_unset) {
+                Some(AttrChange::Toggle { off, on }) => {
+                    // only the flags that changed are written, so the colors are kept
+                    if !off.is_empty() {
+                        out.unset_attr(off)?;
+                    }
+                    if !on.is_empty() {
+                        out.set_attr(on)?;
+                    }
+                    // setting the underline resets its style
+                    if on.is_underline() {
+                        state.underline = Some(Underline::Single);
+                    }
                 }
-                Some(CellAttr::Reset) => {
+                Some(AttrChange::Reset) => {
                     wrote_reset = true;
-                    out.reset_attr()?
+                    out.reset_attr()?;
+                    state.underline = None;
+                    state.underline_color = Some(Color::Reset);
+
+                    // the flags that are kept are set again
+                    let flags = change.attr.flags();
+                    if !flags.is_empty() {
+                        out.set_attr(flags)?;
+                    }
+                    if flags.is_underline() {
+                        state.underline = Some(Underline::Single);
+                    }
+                }
+                None => {}
+            }
+
+            // the underline only matters if the cell is underlined
+            if change.attr.flags().is_underline() {
+                if let Some(style) = state.maybe_underline(change.underline) {
+                    out.set_underline(style)?;
+                }
+
+                match state.maybe_underline_color(change.underline_color) {
+                    Some(Color::Rgb(color)) => out.set_underline_color(color)?,
+                    Some(Color::Blend(color, ..)) => out.set_underline_color(color.to_rgb())?,
+                    Some(Color::Reset) => out.reset_underline_color()?,
+                    _ => {}
                 }
-                _ => {}
             }
 
             match state.maybe_fg(change.fg, wrote_reset) {
                 Some(Color::Rgb(fg)) => out.set_fg(fg)?,
+                Some(Color::Blend(fg, ..)) => out.set_fg(fg.to_rgb())?,
                 Some(Color::Reset) => out.reset_fg()?,
                 _ => {}
             }
 
             match state.maybe_bg(change.bg, wrote_reset) {
                 Some(Color::Rgb(bg)) => out.set_bg(bg)?,
+                Some(Color::Blend(bg, ..)) => out.set_bg(bg.to_rgb())?,
                 Some(Color::Reset) => out.reset_bg()?,
                 _ => {}
             }
 
+            if let Some(link) = state.maybe_link(change.link) {
+                match link.and_then(|_| back.link(&change)) {
+                    Some(Hyperlink { uri, id }) => out.start_link(uri, id)?,
+                    None => out.end_link()?,
+                }
+            }
+
             wrote_reset = false;
-            out.write(change.char)?;
+            match back.grapheme(&change) {
+                Some(grapheme) => out.write_grapheme(grapheme)?,
+                None => out.write(change.char)?,
+            }
         }
 
+        self.back.mark_clean();
+        for layer in &mut self.layers {
+            layer.surface.mark_clean();
+            layer.surface.trim();
+        }
+
+        // interning every frame grows the arenas, the front adopts whatever is drawn
+        self.front.trim();
+        self.back.trim();
+
         if seen {
             // move the cursor back to the beginning.
-            if state.maybe_move(Pos2::ZERO) {
+            if state.maybe_move(Pos2::ZERO, 0) {
                 // this should reduce possible flicker
                 out.move_to(Pos2::ZERO)?;
             }
 
-            // reset the colors so interleaved output is reset
+            // close the link and reset the colors so interleaved output is reset
+            if state.link.is_some() {
+                out.end_link()?;
+            }
             out.reset_bg()?;
             out.reset_fg()?;
             out.reset_attr()?;
@@ -128,41 +441,87 @@ impl Context {
 
         Ok(())
     }
+
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    fn composite_layers(&self) -> Option<Surface> {
+        let mut visible = self.layers.iter().filter(|layer| layer.visible).peekable();
+        visible.peek()?;
+
+        let mut surface = self.back.clone();
+        for layer in visible {
+            surface.composite(&layer.surface);
+            // cells that became transparent reveal what is beneath them
+            surface.merge_dirty(&layer.surface);
+        }
+        Some(surface)
+    }
+}
+
+/// How the attribute changes between cells
+enum AttrChange {
+    /// Reset everything, including the colors, then enable the flags of the next attribute
+    Reset,
+    /// Disable and enable these flags
+    Toggle { off: Attribute, on: Attribute },
 }
 
 #[derive(Debug, Default)]
 struct CursorState {
-    last: Option<Pos2>,
+    next: Option<Pos2>,
     fg: Option<Color>,
     bg: Option<Color>,
     attr: Option<CellAttr>,
+    link: Option<Link>,
+    underline: Option<Underline>,
+    underline_color: Option<Color>,
 }
 
 #[cfg_attr(feature = "profiling", profiling::all_functions)]
 impl CursorState {
-    fn maybe_move(&mut self, pos: Pos2) -> bool {
-        let should_move = match self.last {
-            Some(last) if last.y != pos.y || last.x != pos.x.saturating_sub(1) => true,
-            None => true,
-            _ => false,
-        };
-        self.last = Some(pos);
+    fn maybe_move(&mut self, pos: Pos2, width: u16) -> bool {
+        // writing advances the cursor by the width of what was written
+        let should_move = self.next != Some(pos);
+        self.next = Some(pos2(pos.x + width, pos.y));
         should_move
     }
 
-    fn maybe_attr(&mut self, attr: CellAttr) -> Option<CellAttr> {
-        // log::info!("{attr:?} | {:?}", self.attr);
+    /// `can_unset` is whether the renderer can disable individual flags, otherwise everything is reset
+    fn maybe_attr(&mut self, attr: CellAttr, can_unset: bool) -> Option<AttrChange> {
+        // if the current attribute isn't known, it has to be reset
+        let Some(previous) = self.attr.replace(attr) else {
+            return Some(match attr {
+                CellAttr::Reset => AttrChange::Reset,
+                attr => AttrChange::Toggle {
+                    off: Attribute::default(),
+                    on: attr.flags(),
+                },
+            });
+        };
 
-        match (attr, self.attr) {
-            (CellAttr::Reset, None) => {
-                self.attr.replace(attr);
-                return Some(attr);
-            }
-            (CellAttr::Reset, Some(CellAttr::Reset)) => return None,
-            _ => {}
+        let (previous, next) = (previous.flags(), attr.flags());
+        if previous == next {
+            return None;
+        }
+
+        // disabling a flag may disable its siblings, so they are enabled again
+        let off = previous & !next;
+        if !off.is_empty() && !can_unset {
+            return Some(AttrChange::Reset);
         }
+        let on = (next & !previous) | (next & off.siblings());
+        Some(AttrChange::Toggle { off, on })
+    }
+
+    fn maybe_link(&mut self, link: Option<Link>) -> Option<Option<Link>> {
+        (std::mem::replace(&mut self.link, link) != link).then_some(link)
+    }
 
-        (self.attr.replace(attr) != Some(attr)).then_some(attr)
+    fn maybe_underline(&mut self, style: Underline) -> Option<Underline> {
+        (self.underline.replace(style) != Some(style)).then_some(style)
+    }
+
+    fn maybe_underline_color(&mut self, color: Color) -> Option<Color> {
+        Self::maybe_color(color, false, &mut self.underline_color)
     }
 
     fn maybe_fg(&mut self, color: Color, resetting: bool) -> Option<Color> {
@@ -200,3 +559,205 @@ impl CursorState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geom::rect, style::Rgb};
+
+    /// Records what was written at each position
+    #[derive(Default)]
+    struct Recorder {
+        cursor: Pos2,
+        writes: Vec<(Pos2, char)>,
+        scrolls: Vec<(u16, i32)>,
+        attrs: Vec<String>,
+        can_scroll: bool,
+        can_unset: bool,
+    }
+
+    impl Renderer for Recorder {
+        fn begin(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn end(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn clear_screen(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn supports_scroll(&self) -> bool {
+            self.can_scroll
+        }
+        fn set_scroll_region(&mut self, rows: Range<u16>) -> std::io::Result<()> {
+            self.scrolls.push((rows.start, 0));
+            Ok(())
+        }
+        fn scroll_up(&mut self, lines: u16) -> std::io::Result<()> {
+            self.scrolls.last_mut().unwrap().1 = i32::from(lines);
+            Ok(())
+        }
+        fn scroll_down(&mut self, lines: u16) -> std::io::Result<()> {
+            self.scrolls.last_mut().unwrap().1 = -i32::from(lines);
+            Ok(())
+        }
+        fn move_to(&mut self, pos: Pos2) -> std::io::Result<()> {
+            self.cursor = pos;
+            Ok(())
+        }
+        fn set_fg(&mut self, _color: Rgb) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn set_bg(&mut self, _color: Rgb) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn set_attr(&mut self, attr: Attribute) -> std::io::Result<()> {
+            self.attrs.push(format!("set {attr:?}"));
+            Ok(())
+        }
+        fn supports_unset_attr(&self) -> bool {
+            self.can_unset
+        }
+        fn unset_attr(&mut self, attr: Attribute) -> std::io::Result<()> {
+            self.attrs.push(format!("unset {attr:?}"));
+            Ok(())
+        }
+        fn reset_fg(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn reset_bg(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn reset_attr(&mut self) -> std::io::Result<()> {
+            self.attrs.push(String::from("reset"));
+            Ok(())
+        }
+        fn write(&mut self, char: char) -> std::io::Result<()> {
+            self.writes.push((self.cursor, char));
+            self.cursor.x += 1;
+            Ok(())
+        }
+    }
+
+    fn end_frame(context: &mut Context, can_scroll: bool) -> Recorder {
+        let mut recorder = Recorder {
+            can_scroll,
+            ..Recorder::default()
+        };
+        context.end_frame(&mut recorder).unwrap();
+        recorder
+    }
+
+    #[test]
+    fn only_written_cells_are_drawn() {
+        let mut context = Context::new(rect(vec2(5, 3)));
+        end_frame(&mut context, true);
+
+        context.canvas().put(pos2(3, 1), Cell::new('a'));
+        assert_eq!(end_frame(&mut context, true).writes, [(pos2(3, 1), 'a')]);
+        assert!(end_frame(&mut context, true).writes.is_empty());
+    }
+
+    #[test]
+    fn clearing_a_layer_cell_redraws_beneath() {
+        let mut context = Context::new(rect(vec2(5, 3)));
+        context.canvas().put(pos2(0, 0), Cell::new('x'));
+        context.layer(1).put(pos2(1, 1), Cell::new('P'));
+        assert!(end_frame(&mut context, true)
+            .writes
+            .contains(&(pos2(1, 1), 'P')));
+
+        context.layer_surface(1).unwrap()[pos2(1, 1)] = Cell::TRANSPARENT;
+        assert_eq!(context.frame()[pos2(1, 1)].char, ' ');
+        assert_eq!(end_frame(&mut context, true).writes, [(pos2(1, 1), ' ')]);
+
+        // the layer is clean again
+        assert!(end_frame(&mut context, true).writes.is_empty());
+    }
+
+    #[test]
+    fn scroll_only_writes_the_exposed_rows() {
+        let mut context = Context::new(rect(vec2(3, 3)));
+        for (y, char) in (0..).zip("abc".chars()) {
+            context.canvas().put(pos2(0, y), Cell::new(char));
+        }
+        end_frame(&mut context, true);
+
+        context.scroll(0..3, 1);
+        context.canvas().put(pos2(0, 2), Cell::new('d'));
+        let recorder = end_frame(&mut context, true);
+        assert_eq!(recorder.scrolls, [(0, 1)]);
+        assert_eq!(recorder.writes, [(pos2(0, 2), 'd')]);
+    }
+
+    #[test]
+    fn scroll_repaints_without_support() {
+        let mut context = Context::new(rect(vec2(3, 3)));
+        for (y, char) in (0..).zip("abc".chars()) {
+            context.canvas().put(pos2(0, y), Cell::new(char));
+        }
+        end_frame(&mut context, false);
+
+        context.scroll(0..3, 1);
+        context.canvas().put(pos2(0, 2), Cell::new('d'));
+        let recorder = end_frame(&mut context, false);
+        assert!(recorder.scrolls.is_empty());
+        // the exposed row is reset, so its blanks are written too
+        assert_eq!(
+            recorder.writes,
+            [
+                (pos2(0, 0), 'b'),
+                (pos2(0, 1), 'c'),
+                (pos2(0, 2), 'd'),
+                (pos2(1, 2), ' '),
+                (pos2(2, 2), ' ')
+            ]
+        );
+    }
+
+    #[test]
+    fn scrolling_a_single_row_repaints_it() {
+        let mut context = Context::new(rect(vec2(3, 3)));
+        for (y, char) in (0..).zip("abc".chars()) {
+            context.canvas().put(pos2(0, y), Cell::new(char));
+        }
+        end_frame(&mut context, true);
+
+        context.scroll(1..2, 1);
+        context.canvas().put(pos2(1, 1), Cell::new('d'));
+        let recorder = end_frame(&mut context, true);
+        assert!(recorder.scrolls.is_empty());
+        assert_eq!(
+            recorder.writes,
+            [(pos2(0, 1), ' '), (pos2(1, 1), 'd'), (pos2(2, 1), ' ')]
+        );
+        assert_eq!(context.surface()[pos2(0, 2)].char, 'c');
+    }
+
+    #[test]
+    fn flags_are_set_again_without_unset_attr() {
+        let draw = |can_unset| {
+            let mut context = Context::new(rect(vec2(3, 1)));
+            let mut canvas = context.canvas();
+            canvas.put(
+                pos2(0, 0),
+                Cell::new('a').attr(Attribute::BOLD | Attribute::ITALIC),
+            );
+            canvas.put(pos2(1, 0), Cell::new('b').attr(Attribute::ITALIC));
+            canvas.put(pos2(2, 0), Cell::new('c').attr(Attribute::ITALIC));
+
+            let mut recorder = Recorder {
+                can_unset,
+                ..Recorder::default()
+            };
+            context.end_frame(&mut recorder).unwrap();
+            recorder.attrs
+        };
+
+        assert_eq!(draw(true), ["set Bold + Italic", "unset Bold", "reset"]);
+        assert_eq!(
+            draw(false),
+            ["set Bold + Italic", "reset", "set Italic", "reset"]
+        );
+    }
+}
@@ -23,6 +23,9 @@ pub use rectf::Rectf;
 mod offset;
 pub use offset::{offset, Offset};
 
+mod line;
+pub use line::Line;
+
 mod align;
 pub use align::{Align, Align2};
 
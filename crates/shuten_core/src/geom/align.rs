@@ -40,6 +40,16 @@ impl Align {
         }
     }
 
+    /// Offset `size` inside of the `available` space using this alignment
+    pub const fn offset(self, size: u16, available: u16) -> u16 {
+        let space = available.saturating_sub(size);
+        match self {
+            Self::Min => 0,
+            Self::Center => space / 2,
+            Self::Max => space,
+        }
+    }
+
     pub const fn factor(self) -> f32 {
         match self {
             Self::Min => 0.0,
@@ -86,13 +86,7 @@ pub enum MainAxisAlignItems {
     /// - top-down
     ///     - this is the left side of the container
     Start,
-    /// Align items to the end of the container's main axis
-    ///
-    /// - left-to-right
-    ///     - this is the bottom of the container
-    /// - top-down
-    ///     - this is the right side of the container
-    /// /// Align items to the center of the container's main axis
+    /// Align items to the center of the container's main axis
     Center,
     /// Align items to the end of the container's main axis
     ///
@@ -1,6 +1,9 @@
 use std::f32::consts::TAU;
 
-use crate::style::Rgb;
+use crate::{
+    geom::{Pos2, Rect},
+    style::Rgb,
+};
 
 pub fn gradient(t: f32, a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Rgb {
     let v = a + b * ((c * t + d) * TAU).cos();
@@ -16,6 +19,17 @@ pub struct Gradient {
 }
 
 impl Gradient {
+    /// Sample this gradient at `t` (in the range `0.0 ..= 1.0`)
+    ///
+    /// ```rust
+    /// use shuten_core::geom::gradient::Gradient;
+    /// let (start, end) = (Gradient::RAINBOW1.at(0.0), Gradient::RAINBOW1.at(1.0));
+    /// assert_eq!(start, end);
+    /// ```
+    pub fn at(&self, t: f32) -> Rgb {
+        gradient(t, self.offset, self.amp, self.freq, self.phase)
+    }
+
     pub const RAINBOW1: Self = Self {
         offset: vec3(0.5, 0.5, 0.5),
         amp: vec3(0.5, 0.5, 0.5),
@@ -138,6 +152,220 @@ impl Gradient {
     };
 }
 
+/// The colors sampled by a [`Fill`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ramp {
+    /// Sample a cosine [`Gradient`]
+    Gradient(Gradient),
+    /// Interpolate between colour stops, each stop is a position (in the range `0.0 ..= 1.0`) and a color
+    ///
+    /// The stops should be sorted by their position
+    Stops(Vec<(f32, Rgb)>),
+}
+
+impl Ramp {
+    /// Sample this ramp at `t` (in the range `0.0 ..= 1.0`)
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::gradient::Ramp, style::Rgb};
+    /// let ramp = Ramp::from([Rgb::new(0, 0, 0), Rgb::new(200, 100, 0)]);
+    /// assert_eq!(ramp.at(0.5), Rgb::new(100, 50, 0));
+    /// assert_eq!(ramp.at(2.0), Rgb::new(200, 100, 0));
+    /// ```
+    pub fn at(&self, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let stops = match self {
+            Self::Gradient(gradient) => return gradient.at(t),
+            Self::Stops(stops) => stops,
+        };
+
+        let Some(index) = stops.iter().position(|&(pos, _)| pos >= t) else {
+            return stops.last().map_or_else(Rgb::default, |&(_, color)| color);
+        };
+        let (end, right) = stops[index];
+        let Some(&(start, left)) = index.checked_sub(1).and_then(|i| stops.get(i)) else {
+            return right;
+        };
+
+        if end <= start {
+            return right;
+        }
+        let [r1, g1, b1] = left.as_float();
+        let [r2, g2, b2] = right.as_float();
+        let mix = |a: f32, b: f32| (b - a).mul_add((t - start) / (end - start), a);
+        let scale = |d: f32| (d * 256.0).round().min(255.0) as u8;
+        Rgb::new(scale(mix(r1, r2)), scale(mix(g1, g2)), scale(mix(b1, b2)))
+    }
+}
+
+impl From<Gradient> for Ramp {
+    fn from(value: Gradient) -> Self {
+        Self::Gradient(value)
+    }
+}
+
+impl From<Vec<(f32, Rgb)>> for Ramp {
+    fn from(value: Vec<(f32, Rgb)>) -> Self {
+        Self::Stops(value)
+    }
+}
+
+impl From<&[(f32, Rgb)]> for Ramp {
+    fn from(value: &[(f32, Rgb)]) -> Self {
+        Self::Stops(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<[(f32, Rgb); N]> for Ramp {
+    fn from(value: [(f32, Rgb); N]) -> Self {
+        Self::Stops(value.to_vec())
+    }
+}
+
+/// Evenly spaced colour stops
+impl<const N: usize> From<[Rgb; N]> for Ramp {
+    fn from(value: [Rgb; N]) -> Self {
+        let last = N.saturating_sub(1).max(1) as f32;
+        Self::Stops((0..).map(|i| i as f32 / last).zip(value).collect())
+    }
+}
+
+/// The geometry of a [`Fill`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FillKind {
+    /// A linear fill, the angle is in degrees, `0` goes from left to right and `90` goes from top to bottom
+    Linear(f32),
+    /// A radial fill, from the center of the [`Rect`] out to its edges
+    Radial,
+    /// A conic fill that sweeps clockwise around the center of the [`Rect`], the angle is where it starts, in degrees
+    Conic(f32),
+}
+
+/// Which colors of a [`Cell`](crate::Cell) a [`Fill`] applies to
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FillTarget {
+    /// Only the foreground
+    Fg,
+    /// Only the background
+    #[default]
+    Bg,
+    /// Both the foreground and background
+    Both,
+}
+
+/// A gradient fill, used with [`Canvas::fill_gradient`](crate::Canvas::fill_gradient)
+///
+/// ```rust
+/// use shuten_core::geom::{gradient::{Fill, Gradient}, rect, vec2};
+/// use shuten_core::{style::Rgb, Context};
+///
+/// let mut context = Context::new(rect(vec2(10, 5)));
+/// let mut canvas = context.canvas();
+/// canvas.fill_gradient(canvas.area(), &Fill::linear(Gradient::RAINBOW1, 45.0));
+/// canvas.fill_gradient(canvas.area(), &Fill::radial([Rgb::new(255, 0, 0), Rgb::new(0, 0, 255)]).fg());
+/// canvas.fill_gradient(canvas.area(), &Fill::conic(Gradient::ORANGE_BLUE, 0.0).dither(4));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fill {
+    /// The colors to sample
+    pub ramp: Ramp,
+    /// The geometry of the fill
+    pub kind: FillKind,
+    /// Which colors to fill
+    pub target: FillTarget,
+    dither: Option<u16>,
+}
+
+impl Fill {
+    /// Create a linear fill at an angle, in degrees
+    pub fn linear(ramp: impl Into<Ramp>, angle: f32) -> Self {
+        Self::new(ramp, FillKind::Linear(angle))
+    }
+
+    /// Create a radial fill, from the center out to the edges
+    pub fn radial(ramp: impl Into<Ramp>) -> Self {
+        Self::new(ramp, FillKind::Radial)
+    }
+
+    /// Create a conic fill, starting at an angle, in degrees
+    pub fn conic(ramp: impl Into<Ramp>, angle: f32) -> Self {
+        Self::new(ramp, FillKind::Conic(angle))
+    }
+
+    /// Create a new fill of this [`FillKind`]
+    ///
+    /// By default, only the background is filled
+    pub fn new(ramp: impl Into<Ramp>, kind: FillKind) -> Self {
+        Self {
+            ramp: ramp.into(),
+            kind,
+            target: FillTarget::default(),
+            dither: None,
+        }
+    }
+
+    /// Only fill the foreground
+    pub fn fg(self) -> Self {
+        self.target(FillTarget::Fg)
+    }
+
+    /// Only fill the background
+    pub fn bg(self) -> Self {
+        self.target(FillTarget::Bg)
+    }
+
+    /// Fill both the foreground and background
+    pub fn both(self) -> Self {
+        self.target(FillTarget::Both)
+    }
+
+    /// Set which colors are filled
+    pub fn target(mut self, target: FillTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Quantize the ramp to `levels` colors, and dither between them with `░▒▓`
+    ///
+    /// Fewer than 2 colors are treated as 2
+    ///
+    /// - [`FillTarget::Both`] replaces the characters with shades of the next color over the current one
+    /// - [`FillTarget::Fg`] replaces the characters with shades, over the existing background
+    /// - [`FillTarget::Bg`] keeps the characters, and uses the nearest color
+    pub fn dither(mut self, levels: u16) -> Self {
+        self.dither = Some(levels.max(2));
+        self
+    }
+
+    /// How many colors the ramp is quantized to, if this fill is [dithered](Self::dither)
+    pub const fn dither_levels(&self) -> Option<u16> {
+        self.dither
+    }
+
+    /// Where does this [`point`](Pos2) in the [`Rect`] sample the ramp (in the range `0.0 ..= 1.0`)
+    ///
+    /// Positions are normalized to the [`Rect`], so the fill stretches to its aspect ratio
+    pub fn t(&self, rect: Rect, pos: Pos2) -> f32 {
+        let normalize = |d: u16, min: u16, size: u16| {
+            (f32::from(d.saturating_sub(min)) + 0.5) / f32::from(size.max(1))
+        };
+        let u = normalize(pos.x, rect.left(), rect.width());
+        let v = normalize(pos.y, rect.top(), rect.height());
+        let (dx, dy) = (u - 0.5, v - 0.5);
+
+        let t = match self.kind {
+            FillKind::Linear(angle) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let extent = 0.5 * (sin.abs() + cos.abs());
+                (dx.mul_add(cos, dy * sin) / extent + 1.0) * 0.5
+            }
+            FillKind::Radial => dx.hypot(dy) * 2.0,
+            FillKind::Conic(angle) => ((dy.atan2(dx) - angle.to_radians()) / TAU).rem_euclid(1.0),
+        };
+        t.clamp(0.0, 1.0)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct Vec3 {
     pub x: f32,
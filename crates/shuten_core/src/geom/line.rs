@@ -0,0 +1,86 @@
+use super::{pos2, Pos2};
+
+/// An iterator over each [`Pos2`] on a line, from `start` to `end` (inclusive)
+///
+/// This uses Bresenham's line algorithm, so it can also be used for hit-testing
+///
+/// ```rust
+/// use shuten_core::geom::{pos2, Line};
+/// let line = Line::new(pos2(0, 0), pos2(4, 2));
+/// assert_eq!(line.len(), 5);
+/// assert!(line.clone().any(|pos| pos == pos2(2, 1)));
+/// assert_eq!(line.last(), Some(pos2(4, 2)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Line {
+    x: i32,
+    y: i32,
+    end_x: i32,
+    end_y: i32,
+    dx: i32,
+    dy: i32,
+    step_x: i32,
+    step_y: i32,
+    error: i32,
+    done: bool,
+}
+
+impl Line {
+    /// Create a line from `start` to `end`
+    pub fn new(start: Pos2, end: Pos2) -> Self {
+        let (x, y) = (i32::from(start.x), i32::from(start.y));
+        let (end_x, end_y) = (i32::from(end.x), i32::from(end.y));
+        let (dx, dy) = ((end_x - x).abs(), -(end_y - y).abs());
+        Self {
+            x,
+            y,
+            end_x,
+            end_y,
+            dx,
+            dy,
+            step_x: (end_x - x).signum(),
+            step_y: (end_y - y).signum(),
+            error: dx + dy,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Line {
+    type Item = Pos2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let pos = pos2(self.x as u16, self.y as u16);
+        if self.x == self.end_x && self.y == self.end_y {
+            self.done = true;
+            return Some(pos);
+        }
+
+        let error = 2 * self.error;
+        if error >= self.dy {
+            self.error += self.dy;
+            self.x += self.step_x;
+        }
+        if error <= self.dx {
+            self.error += self.dx;
+            self.y += self.step_y;
+        }
+        Some(pos)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.done {
+            true => 0,
+            false => (self.end_x - self.x).abs().max((self.end_y - self.y).abs()) as usize + 1,
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+
+impl std::iter::FusedIterator for Line {}
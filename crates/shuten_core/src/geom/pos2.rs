@@ -1,4 +1,4 @@
-use super::{lerp, offset, vec2, Offset, Vec2};
+use super::{lerp, offset, vec2, Line, Offset, Vec2};
 
 /// Create a new [`Pos2`] with `x` and `y`
 pub const fn pos2(x: u16, y: u16) -> Pos2 {
@@ -82,6 +82,11 @@ impl Pos2 {
         pos2(lerp(self.x..=other.x, t), lerp(self.y..=other.y, t))
     }
 
+    /// Get an iterator over each point on a [`Line`] from this point to the other point
+    pub fn line_to(self, other: Self) -> Line {
+        Line::new(self, other)
+    }
+
     /// Convert this position to an [`Offset`]
     pub const fn as_offset(self) -> Offset {
         offset(self.x as i32, self.y as i32)
@@ -1,10 +1,24 @@
+pub mod border;
 pub mod geom;
 pub mod layout;
+pub mod pixel;
 pub mod renderer;
+pub mod shape;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod sprite;
 pub mod style;
+pub mod text;
 
 mod context;
 pub use context::Context;
 
 mod surface;
-pub use surface::{Canvas, Cell, Surface};
+pub use surface::{Canvas, Cell, Connectivity, Grapheme, Hyperlink, Link, Surface};
+
+// these can be moved to another thread
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Surface>();
+    assert_send::<Context>();
+};
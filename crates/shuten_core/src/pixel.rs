@@ -0,0 +1,268 @@
+//! A higher-resolution bitmap that is drawn onto a [`Canvas`] using sub-cell characters
+//!
+//! ```rust
+//! use shuten_core::geom::{pos2f, rect, vec2};
+//! use shuten_core::pixel::{PixelCanvas, PixelMode};
+//! use shuten_core::Context;
+//!
+//! let mut context = Context::new(rect(vec2(10, 5)));
+//! let mut pixels = PixelCanvas::new(PixelMode::Braille, vec2(10, 5));
+//! assert_eq!(pixels.resolution(), vec2(20, 20));
+//!
+//! pixels.line(pos2f(0.0, 0.0), pos2f(19.0, 19.0), 0xFF0000);
+//! pixels.draw(&mut context.canvas());
+//! ```
+use crate::{
+    geom::{pos2, vec2, Pos2f, Rectf, Vec2},
+    style::Color,
+    Canvas, Cell,
+};
+
+/// How pixels are packed into a [`Cell`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum PixelMode {
+    /// Braille characters, `2x4` pixels per cell (e.g. `⣿`)
+    ///
+    /// Each cell has a single color
+    #[default]
+    Braille,
+    /// Half-blocks, `1x2` pixels per cell (e.g. `▀`)
+    ///
+    /// Each pixel has its own color
+    HalfBlock,
+    /// Quadrants, `2x2` pixels per cell (e.g. `▚`)
+    ///
+    /// Each cell has a single color
+    Quadrant,
+    /// Sextants, `2x3` pixels per cell (e.g. `🬗`)
+    ///
+    /// Each cell has a single color
+    Sextant,
+}
+
+impl PixelMode {
+    /// How many pixels fit in a single [`Cell`]
+    pub const fn pixels_per_cell(self) -> Vec2 {
+        match self {
+            Self::Braille => vec2(2, 4),
+            Self::HalfBlock => vec2(1, 2),
+            Self::Quadrant => vec2(2, 2),
+            Self::Sextant => vec2(2, 3),
+        }
+    }
+
+    fn char(self, mask: u8) -> char {
+        const QUADRANTS: [char; 16] = [
+            ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+        ];
+
+        match self {
+            Self::Braille => {
+                // braille dots are numbered down the left column, then down the right column
+                // with the last row added later on
+                const DOTS: [u8; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+                let bits = (0..8)
+                    .filter(|bit| mask & (1 << bit) != 0)
+                    .fold(0, |bits, bit| bits | DOTS[bit]);
+                char::from_u32(0x2800 + bits as u32).unwrap_or(' ')
+            }
+            Self::Quadrant => QUADRANTS[mask as usize & 0xF],
+            Self::Sextant => match mask & 0x3F {
+                0 => ' ',
+                0b01_0101 => '▌',
+                0b10_1010 => '▐',
+                0b11_1111 => '█',
+                // the sextant block skips the characters that already exist as half-blocks
+                mask => {
+                    let skipped = (mask > 0b01_0101) as u32 + (mask > 0b10_1010) as u32;
+                    char::from_u32(0x1FB00 + mask as u32 - 1 - skipped).unwrap_or(' ')
+                }
+            },
+            Self::HalfBlock => QUADRANTS[(mask as usize & 0b01) * 3 + (mask as usize & 0b10) * 6],
+        }
+    }
+}
+
+/// A higher-resolution bitmap that is drawn onto a [`Canvas`]
+///
+/// Positions are in pixels, see [`PixelCanvas::resolution`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PixelCanvas {
+    mode: PixelMode,
+    size: Vec2,
+    pixels: Vec<Option<Color>>,
+}
+
+impl PixelCanvas {
+    /// Create a new, empty, pixel canvas that covers `size` cells
+    pub fn new(mode: PixelMode, size: Vec2) -> Self {
+        let resolution = size * mode.pixels_per_cell();
+        Self {
+            mode,
+            size,
+            pixels: vec![None; resolution.x as usize * resolution.y as usize],
+        }
+    }
+
+    /// The [`PixelMode`] of this pixel canvas
+    pub const fn mode(&self) -> PixelMode {
+        self.mode
+    }
+
+    /// How many cells this pixel canvas covers
+    pub const fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// How many pixels this pixel canvas has
+    pub fn resolution(&self) -> Vec2 {
+        self.size * self.mode.pixels_per_cell()
+    }
+
+    /// Resize this pixel canvas to cover `size` cells
+    ///
+    /// This clears all of the pixels
+    pub fn resize(&mut self, size: Vec2) {
+        *self = Self::new(self.mode, size)
+    }
+
+    /// Clear all of the pixels
+    pub fn clear(&mut self) {
+        self.pixels.fill(None)
+    }
+
+    /// Set the pixel at this position to the provided [`Color`]
+    pub fn set(&mut self, pos: Pos2f, color: impl Into<Color>) {
+        if let Some(index) = self.index(pos) {
+            self.pixels[index] = Some(color.into())
+        }
+    }
+
+    /// Unset the pixel at this position
+    pub fn unset(&mut self, pos: Pos2f) {
+        if let Some(index) = self.index(pos) {
+            self.pixels[index] = None
+        }
+    }
+
+    /// Get the [`Color`] of the pixel at this position, if its set
+    pub fn get(&self, pos: Pos2f) -> Option<Color> {
+        self.index(pos).and_then(|index| self.pixels[index])
+    }
+
+    /// Draw a line of pixels from `start` to `end` (inclusive)
+    pub fn line(&mut self, start: Pos2f, end: Pos2f, color: impl Into<Color>) {
+        let color = color.into();
+        let delta = end - start;
+        let steps = delta.x.abs().max(delta.y.abs()).ceil().max(1.0);
+        for i in 0..=steps as u32 {
+            let t = i as f32 / steps;
+            self.set(start + delta.to_vec2() * t, color)
+        }
+    }
+
+    /// Fill the [`Rectf`] with pixels
+    pub fn fill_rect(&mut self, rect: Rectf, color: impl Into<Color>) {
+        let color = color.into();
+        let resolution = self.resolution();
+        let clamp = |d: f32, max: u16| d.clamp(0.0, max as f32) as u16;
+
+        let (left, right) = (
+            clamp(rect.left().floor(), resolution.x),
+            clamp(rect.right().ceil(), resolution.x),
+        );
+        let (top, bottom) = (
+            clamp(rect.top().floor(), resolution.y),
+            clamp(rect.bottom().ceil(), resolution.y),
+        );
+
+        for y in top..bottom {
+            for x in left..right {
+                let index = y as usize * resolution.x as usize + x as usize;
+                self.pixels[index] = Some(color);
+            }
+        }
+    }
+
+    /// Draw the pixels onto the [`Canvas`], starting at its `pos2(0, 0)`
+    ///
+    /// Cells without any pixels set are left untouched
+    pub fn draw(&self, canvas: &mut Canvas) {
+        let per = self.mode.pixels_per_cell();
+
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                let pixels = (0..per.y).flat_map(|py| (0..per.x).map(move |px| (px, py)));
+                let pixels = pixels.map(|(px, py)| {
+                    let (px, py) = (x * per.x + px, y * per.y + py);
+                    self.pixels[py as usize * (self.size.x * per.x) as usize + px as usize]
+                });
+
+                if let Some(cell) = self.resolve(pixels) {
+                    canvas.put(pos2(x, y), cell)
+                }
+            }
+        }
+    }
+
+    /// Draw the pixels onto the [`Canvas`], then clear them
+    pub fn flush(&mut self, canvas: &mut Canvas) {
+        self.draw(canvas);
+        self.clear();
+    }
+
+    fn resolve(&self, pixels: impl Iterator<Item = Option<Color>>) -> Option<Cell> {
+        let mut mask = 0_u8;
+        let mut colors = [(Color::Reuse, 0_u8); 8];
+        let mut seen = 0;
+        let mut halves = [None; 2];
+
+        for (bit, pixel) in pixels.enumerate() {
+            let Some(color) = pixel else { continue };
+            mask |= 1 << bit;
+            if let Some(half) = halves.get_mut(bit) {
+                *half = Some(color)
+            }
+
+            match colors[..seen].iter_mut().find(|(c, _)| *c == color) {
+                Some((_, count)) => *count += 1,
+                None => {
+                    colors[seen] = (color, 1);
+                    seen += 1;
+                }
+            }
+        }
+
+        if mask == 0 {
+            return None;
+        }
+
+        if self.mode == PixelMode::HalfBlock {
+            return Some(match halves {
+                [Some(top), Some(bottom)] => Cell::new('▀').fg(top).bg(bottom),
+                [Some(top), None] => Cell::new('▀').fg(top),
+                [None, Some(bottom)] => Cell::new('▄').fg(bottom),
+                [None, None] => return None,
+            });
+        }
+
+        // the most common color is used for the entire cell
+        let (fg, _) =
+            colors[..seen].iter().fold(
+                colors[0],
+                |max, &next| if next.1 > max.1 { next } else { max },
+            );
+
+        Some(Cell::new(self.mode.char(mask)).fg(fg))
+    }
+
+    fn index(&self, pos: Pos2f) -> Option<usize> {
+        let resolution = self.resolution();
+        let (x, y) = (pos.x.floor(), pos.y.floor());
+        if x < 0.0 || y < 0.0 || x >= resolution.x as f32 || y >= resolution.y as f32 {
+            return None;
+        }
+        Some(y as usize * resolution.x as usize + x as usize)
+    }
+}
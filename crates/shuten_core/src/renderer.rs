@@ -6,11 +6,11 @@
 //!
 //! A no-op renderer is also provided:
 //! - [`NullRenderer`]
-use std::io::Result;
+use std::{io::Result, ops::Range};
 
 use crate::{
     geom::Pos2,
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
 };
 
 /// A renderer is a simple abstraction over *what* operations are done to a terminal
@@ -23,6 +23,34 @@ pub trait Renderer {
     /// Clear the current buffer
     fn clear_screen(&mut self) -> Result<()>;
 
+    /// Can this renderer scroll the terminal?
+    ///
+    /// If it can't, the scrolled rows are written again instead
+    fn supports_scroll(&self) -> bool {
+        false
+    }
+
+    /// Limit scrolling to these rows
+    ///
+    /// This moves the cursor to the left-top of the terminal
+    fn set_scroll_region(&mut self, _rows: Range<u16>) -> Result<()> {
+        Ok(())
+    }
+    /// Reset the scroll region to the entire terminal
+    ///
+    /// This moves the cursor to the left-top of the terminal
+    fn reset_scroll_region(&mut self) -> Result<()> {
+        Ok(())
+    }
+    /// Scroll the scroll region up by this many lines, blank lines appear at the bottom
+    fn scroll_up(&mut self, _lines: u16) -> Result<()> {
+        Ok(())
+    }
+    /// Scroll the scroll region down by this many lines, blank lines appear at the top
+    fn scroll_down(&mut self, _lines: u16) -> Result<()> {
+        Ok(())
+    }
+
     /// Move the cursor to [`pos`](Pos2)
     fn move_to(&mut self, pos: Pos2) -> Result<()>;
 
@@ -33,6 +61,20 @@ pub trait Renderer {
     /// Set the attribute to [`Attribute`]
     fn set_attr(&mut self, attr: Attribute) -> Result<()>;
 
+    /// Can this renderer disable individual flags?
+    ///
+    /// If it can't, the attribute is [reset](Renderer::reset_attr) and the remaining flags are set again
+    fn supports_unset_attr(&self) -> bool {
+        false
+    }
+
+    /// Disable the flags of this [`Attribute`], keeping the colors and any other flags
+    ///
+    /// Some flags are disabled together, see [`Attribute::siblings`]
+    fn unset_attr(&mut self, _attr: Attribute) -> Result<()> {
+        Ok(())
+    }
+
     /// Reset the foreground to the terminal default
     fn reset_fg(&mut self) -> Result<()>;
     /// Reset the background to the terminal default
@@ -40,9 +82,42 @@ pub trait Renderer {
     /// Reset the current attribute
     fn reset_attr(&mut self) -> Result<()>;
 
+    /// Set the [style](Underline) of the underline
+    ///
+    /// This is only used when the current attribute has [`Attribute::UNDERLINE`]
+    fn set_underline(&mut self, _style: Underline) -> Result<()> {
+        Ok(())
+    }
+    /// Set the underline color to [`Rgb`]
+    fn set_underline_color(&mut self, _color: Rgb) -> Result<()> {
+        Ok(())
+    }
+    /// Reset the underline color to the foreground
+    fn reset_underline_color(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Write a character to the terminal
     fn write(&mut self, char: char) -> Result<()>;
 
+    /// Write a grapheme cluster to the terminal
+    ///
+    /// This is a single user-perceived character made up of multiple code points
+    fn write_grapheme(&mut self, grapheme: &str) -> Result<()> {
+        grapheme.chars().try_for_each(|char| self.write(char))
+    }
+
+    /// Start a hyperlink, anything written after this is part of the link
+    ///
+    /// Starting a new link ends the current one
+    fn start_link(&mut self, _uri: &str, _id: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+    /// End the current hyperlink
+    fn end_link(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Set the title of the terminal
     fn set_title(&mut self, _title: &str) -> Result<()> {
         Ok(())
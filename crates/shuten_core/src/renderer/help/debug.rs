@@ -1,9 +1,9 @@
-use std::{io::Result, str::Utf8Error};
+use std::{io::Result, ops::Range, str::Utf8Error};
 
 use crate::{
     geom::Pos2,
     renderer::{Renderer, TermRenderer},
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
     Canvas, Context,
 };
 
@@ -85,6 +85,26 @@ impl Renderer for DebugRenderer {
         self.0.clear_screen()
     }
 
+    fn supports_scroll(&self) -> bool {
+        self.0.supports_scroll()
+    }
+
+    fn set_scroll_region(&mut self, rows: Range<u16>) -> Result<()> {
+        self.0.set_scroll_region(rows)
+    }
+
+    fn reset_scroll_region(&mut self) -> Result<()> {
+        self.0.reset_scroll_region()
+    }
+
+    fn scroll_up(&mut self, lines: u16) -> Result<()> {
+        self.0.scroll_up(lines)
+    }
+
+    fn scroll_down(&mut self, lines: u16) -> Result<()> {
+        self.0.scroll_down(lines)
+    }
+
     fn move_to(&mut self, pos: Pos2) -> Result<()> {
         self.0.move_to(pos)
     }
@@ -101,6 +121,14 @@ impl Renderer for DebugRenderer {
         self.0.set_attr(attr)
     }
 
+    fn supports_unset_attr(&self) -> bool {
+        self.0.supports_unset_attr()
+    }
+
+    fn unset_attr(&mut self, attr: Attribute) -> Result<()> {
+        self.0.unset_attr(attr)
+    }
+
     fn reset_fg(&mut self) -> Result<()> {
         self.0.reset_fg()
     }
@@ -113,10 +141,34 @@ impl Renderer for DebugRenderer {
         self.0.reset_attr()
     }
 
+    fn set_underline(&mut self, style: Underline) -> Result<()> {
+        self.0.set_underline(style)
+    }
+
+    fn set_underline_color(&mut self, color: Rgb) -> Result<()> {
+        self.0.set_underline_color(color)
+    }
+
+    fn reset_underline_color(&mut self) -> Result<()> {
+        self.0.reset_underline_color()
+    }
+
     fn write(&mut self, char: char) -> Result<()> {
         self.0.write(char)
     }
 
+    fn write_grapheme(&mut self, grapheme: &str) -> Result<()> {
+        self.0.write_grapheme(grapheme)
+    }
+
+    fn start_link(&mut self, uri: &str, id: Option<&str>) -> Result<()> {
+        self.0.start_link(uri, id)
+    }
+
+    fn end_link(&mut self) -> Result<()> {
+        self.0.end_link()
+    }
+
     fn set_title(&mut self, title: &str) -> Result<()> {
         self.0.set_title(title)
     }
@@ -1,9 +1,9 @@
-use std::string::FromUtf8Error;
+use std::{ops::Range, string::FromUtf8Error};
 
 use crate::{
     geom::Pos2,
     renderer::Renderer,
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
     Canvas, Context,
 };
 
@@ -93,6 +93,30 @@ impl<W: std::io::Write> Renderer for ExplainRenderer<W> {
         Ok(())
     }
 
+    fn supports_scroll(&self) -> bool {
+        true
+    }
+
+    fn set_scroll_region(&mut self, rows: Range<u16>) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  set scroll region {rows:?}")
+    }
+
+    fn reset_scroll_region(&mut self) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  reset scroll region")
+    }
+
+    fn scroll_up(&mut self, lines: u16) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  scroll up {lines}")
+    }
+
+    fn scroll_down(&mut self, lines: u16) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  scroll down {lines}")
+    }
+
     fn move_to(&mut self, pos: Pos2) -> std::io::Result<()> {
         self.next_entry()?;
         writeln!(&mut self.out, "  move to {pos:?}")
@@ -113,6 +137,15 @@ impl<W: std::io::Write> Renderer for ExplainRenderer<W> {
         writeln!(&mut self.out, "  set attr {attr:?}")
     }
 
+    fn supports_unset_attr(&self) -> bool {
+        true
+    }
+
+    fn unset_attr(&mut self, attr: Attribute) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  unset attr {attr:?}")
+    }
+
     fn reset_fg(&mut self) -> std::io::Result<()> {
         self.next_entry()?;
         writeln!(&mut self.out, "  reset fg")
@@ -128,6 +161,21 @@ impl<W: std::io::Write> Renderer for ExplainRenderer<W> {
         writeln!(&mut self.out, "  reset attr")
     }
 
+    fn set_underline(&mut self, style: Underline) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  set underline {style:?}")
+    }
+
+    fn set_underline_color(&mut self, color: Rgb) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  set underline color {color:?}")
+    }
+
+    fn reset_underline_color(&mut self) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  reset underline color")
+    }
+
     fn write(&mut self, char: char) -> std::io::Result<()> {
         if !self.incomplete {
             write!(&mut self.out, "    ")?;
@@ -141,6 +189,27 @@ impl<W: std::io::Write> Renderer for ExplainRenderer<W> {
         write!(&mut self.out, "{}", d.escape_debug())
     }
 
+    fn write_grapheme(&mut self, grapheme: &str) -> std::io::Result<()> {
+        if !self.incomplete {
+            write!(&mut self.out, "    ")?;
+        }
+        self.incomplete = true;
+        write!(&mut self.out, "{}", grapheme.escape_debug())
+    }
+
+    fn start_link(&mut self, uri: &str, id: Option<&str>) -> std::io::Result<()> {
+        self.next_entry()?;
+        match id {
+            Some(id) => writeln!(&mut self.out, "  start link {uri} ({id})"),
+            None => writeln!(&mut self.out, "  start link {uri}"),
+        }
+    }
+
+    fn end_link(&mut self) -> std::io::Result<()> {
+        self.next_entry()?;
+        writeln!(&mut self.out, "  end link")
+    }
+
     fn set_title(&mut self, _: &str) -> std::io::Result<()> {
         Ok(())
     }
@@ -2,7 +2,8 @@ use std::collections::VecDeque;
 
 use crate::{
     geom::{vec2, Rect},
-    Canvas, Cell,
+    text::{Text, Wrap},
+    Canvas,
 };
 
 mod renderer;
@@ -93,6 +94,7 @@ impl<'a, const N: usize, T> IntoIterator for &'a Queue<T, N> {
 #[derive(Debug, PartialEq)]
 pub struct FrameStats<const N: usize = STATS_WINDOW> {
     pub clears: Queue<usize, N>,
+    pub scrolls: Queue<usize, N>,
     pub moves: Queue<usize, N>,
     pub set_fg: Queue<usize, N>,
     pub set_bg: Queue<usize, N>,
@@ -107,6 +109,7 @@ impl<const N: usize> FrameStats<N> {
     pub fn new() -> Self {
         Self {
             clears: Queue::new(),
+            scrolls: Queue::new(),
             moves: Queue::new(),
             set_fg: Queue::new(),
             set_bg: Queue::new(),
@@ -120,6 +123,7 @@ impl<const N: usize> FrameStats<N> {
 
     pub fn new_frame(&mut self) {
         self.clears.push(0);
+        self.scrolls.push(0);
         self.moves.push(0);
         self.set_fg.push(0);
         self.set_bg.push(0);
@@ -135,6 +139,7 @@ impl Default for FrameStats<STATS_WINDOW> {
     fn default() -> Self {
         Self {
             clears: Queue::new(),
+            scrolls: Queue::new(),
             moves: Queue::new(),
             set_fg: Queue::new(),
             set_bg: Queue::new(),
@@ -150,6 +155,7 @@ impl Default for FrameStats<STATS_WINDOW> {
 #[derive(Debug, Default, Copy, Clone)]
 pub struct FrameStatsConfig {
     pub clears: bool,
+    pub scrolls: bool,
     pub moves: bool,
     pub set_fg: bool,
     pub set_bg: bool,
@@ -164,6 +170,7 @@ impl FrameStatsConfig {
     pub const fn all() -> Self {
         Self {
             clears: true,
+            scrolls: true,
             moves: true,
             set_fg: true,
             set_bg: true,
@@ -186,6 +193,7 @@ impl FrameStatsConfig {
             (self.reset_bg, "reset_bg", &stats.reset_bg),
             (self.reset_attr, "reset_attr", &stats.reset_attr),
             (self.clears, "clears", &stats.clears),
+            (self.scrolls, "scrolls", &stats.scrolls),
         ]
         .into_iter()
         .flat_map(|(ok, label, stats)| ok.then(|| (label, stats.stats())))
@@ -241,29 +249,14 @@ impl<const N: usize> FrameStats<N> {
             let x = data.lines().map(|c| c.len()).max().unwrap_or(1) + 1;
             let y = data.lines().count();
             Rect::from_min_size(canvas.area().left_top(), vec2(x as u16, y as u16))
+                .intersect(canvas.area())
         };
 
         canvas.fill_rect(rect, u32::MIN);
-
-        let mut canvas = canvas.crop(rect);
-        let area = canvas.area();
-        let mut start = area.left_top();
-        for ch in data.chars() {
-            if ch == '\n' {
-                start.y += 1;
-                start.x = area.left();
-                continue;
-            }
-            if start.x >= area.right() {
-                start.y += 1;
-                start.x = area.left()
-            }
-            if start.y > area.bottom() {
-                break;
-            }
-            canvas.put(start, Cell::new(ch).fg(u32::MAX).bg(u32::MIN));
-            start.x += 1;
-        }
+        canvas.text(
+            rect,
+            Text::new(&data).fg(u32::MAX).bg(u32::MIN).wrap(Wrap::Char),
+        );
     }
 }
 
@@ -1,10 +1,10 @@
-use std::io::Result;
+use std::{io::Result, ops::Range};
 
 use super::{FrameStats, StatsWindow as _};
 use crate::renderer::Renderer;
 use crate::{
     geom::Pos2,
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
 };
 
 pub struct MetricsRenderer<'a, R: Renderer, const N: usize> {
@@ -33,6 +33,28 @@ impl<'a, R: Renderer, const N: usize> Renderer for MetricsRenderer<'a, R, N> {
         self.renderer.clear_screen()
     }
 
+    fn supports_scroll(&self) -> bool {
+        self.renderer.supports_scroll()
+    }
+
+    fn set_scroll_region(&mut self, rows: Range<u16>) -> Result<()> {
+        self.renderer.set_scroll_region(rows)
+    }
+
+    fn reset_scroll_region(&mut self) -> Result<()> {
+        self.renderer.reset_scroll_region()
+    }
+
+    fn scroll_up(&mut self, lines: u16) -> Result<()> {
+        self.stats.scrolls.modify(|d| *d += 1);
+        self.renderer.scroll_up(lines)
+    }
+
+    fn scroll_down(&mut self, lines: u16) -> Result<()> {
+        self.stats.scrolls.modify(|d| *d += 1);
+        self.renderer.scroll_down(lines)
+    }
+
     fn move_to(&mut self, pos: Pos2) -> Result<()> {
         self.stats.moves.modify(|d| *d += 1);
         self.renderer.move_to(pos)
@@ -53,6 +75,15 @@ impl<'a, R: Renderer, const N: usize> Renderer for MetricsRenderer<'a, R, N> {
         self.renderer.set_attr(attr)
     }
 
+    fn supports_unset_attr(&self) -> bool {
+        self.renderer.supports_unset_attr()
+    }
+
+    fn unset_attr(&mut self, attr: Attribute) -> Result<()> {
+        self.stats.set_attr.modify(|d| *d += 1);
+        self.renderer.unset_attr(attr)
+    }
+
     fn reset_fg(&mut self) -> Result<()> {
         self.stats.reset_fg.modify(|d| *d += 1);
         self.renderer.reset_fg()
@@ -68,11 +99,36 @@ impl<'a, R: Renderer, const N: usize> Renderer for MetricsRenderer<'a, R, N> {
         self.renderer.reset_attr()
     }
 
+    fn set_underline(&mut self, style: Underline) -> Result<()> {
+        self.renderer.set_underline(style)
+    }
+
+    fn set_underline_color(&mut self, color: Rgb) -> Result<()> {
+        self.renderer.set_underline_color(color)
+    }
+
+    fn reset_underline_color(&mut self) -> Result<()> {
+        self.renderer.reset_underline_color()
+    }
+
     fn write(&mut self, char: char) -> Result<()> {
         self.stats.write.modify(|d| *d += 1);
         self.renderer.write(char)
     }
 
+    fn write_grapheme(&mut self, grapheme: &str) -> Result<()> {
+        self.stats.write.modify(|d| *d += 1);
+        self.renderer.write_grapheme(grapheme)
+    }
+
+    fn start_link(&mut self, uri: &str, id: Option<&str>) -> Result<()> {
+        self.renderer.start_link(uri, id)
+    }
+
+    fn end_link(&mut self) -> Result<()> {
+        self.renderer.end_link()
+    }
+
     fn set_title(&mut self, title: &str) -> Result<()> {
         self.renderer.set_title(title)
     }
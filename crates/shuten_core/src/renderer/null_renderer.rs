@@ -1,9 +1,9 @@
-use std::io::Result;
+use std::{io::Result, ops::Range};
 
 use crate::{
     geom::Pos2,
     renderer::Renderer,
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
 };
 
 /// A no-op [`Renderer`]
@@ -22,6 +22,26 @@ impl Renderer for NullRenderer {
         Ok(())
     }
 
+    fn supports_scroll(&self) -> bool {
+        true
+    }
+
+    fn set_scroll_region(&mut self, _rows: Range<u16>) -> Result<()> {
+        Ok(())
+    }
+
+    fn reset_scroll_region(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn scroll_up(&mut self, _lines: u16) -> Result<()> {
+        Ok(())
+    }
+
+    fn scroll_down(&mut self, _lines: u16) -> Result<()> {
+        Ok(())
+    }
+
     fn move_to(&mut self, _pos: Pos2) -> Result<()> {
         Ok(())
     }
@@ -38,6 +58,14 @@ impl Renderer for NullRenderer {
         Ok(())
     }
 
+    fn supports_unset_attr(&self) -> bool {
+        true
+    }
+
+    fn unset_attr(&mut self, _attr: Attribute) -> Result<()> {
+        Ok(())
+    }
+
     fn reset_fg(&mut self) -> Result<()> {
         Ok(())
     }
@@ -50,10 +78,34 @@ impl Renderer for NullRenderer {
         Ok(())
     }
 
+    fn set_underline(&mut self, _style: Underline) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_underline_color(&mut self, _color: Rgb) -> Result<()> {
+        Ok(())
+    }
+
+    fn reset_underline_color(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     fn write(&mut self, _char: char) -> Result<()> {
         Ok(())
     }
 
+    fn write_grapheme(&mut self, _grapheme: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn start_link(&mut self, _uri: &str, _id: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+
+    fn end_link(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     fn set_title(&mut self, _title: &str) -> Result<()> {
         Ok(())
     }
@@ -1,10 +1,10 @@
 use crate::{
     geom::Pos2,
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
 };
 
 use super::Renderer;
-use std::io::Result;
+use std::{io::Result, ops::Range};
 
 pub struct TeeRenderer<L: Renderer, R: Renderer> {
     left: L,
@@ -37,6 +37,30 @@ impl<L: Renderer, R: Renderer> Renderer for TeeRenderer<L, R> {
         self.right.clear_screen()
     }
 
+    fn supports_scroll(&self) -> bool {
+        self.left.supports_scroll() && self.right.supports_scroll()
+    }
+
+    fn set_scroll_region(&mut self, rows: Range<u16>) -> Result<()> {
+        self.left.set_scroll_region(rows.clone())?;
+        self.right.set_scroll_region(rows)
+    }
+
+    fn reset_scroll_region(&mut self) -> Result<()> {
+        self.left.reset_scroll_region()?;
+        self.right.reset_scroll_region()
+    }
+
+    fn scroll_up(&mut self, lines: u16) -> Result<()> {
+        self.left.scroll_up(lines)?;
+        self.right.scroll_up(lines)
+    }
+
+    fn scroll_down(&mut self, lines: u16) -> Result<()> {
+        self.left.scroll_down(lines)?;
+        self.right.scroll_down(lines)
+    }
+
     fn move_to(&mut self, pos: Pos2) -> Result<()> {
         self.left.move_to(pos)?;
         self.right.move_to(pos)
@@ -57,6 +81,15 @@ impl<L: Renderer, R: Renderer> Renderer for TeeRenderer<L, R> {
         self.right.set_attr(attr)
     }
 
+    fn supports_unset_attr(&self) -> bool {
+        self.left.supports_unset_attr() && self.right.supports_unset_attr()
+    }
+
+    fn unset_attr(&mut self, attr: Attribute) -> Result<()> {
+        self.left.unset_attr(attr)?;
+        self.right.unset_attr(attr)
+    }
+
     fn reset_fg(&mut self) -> Result<()> {
         self.left.reset_fg()?;
         self.right.reset_fg()
@@ -72,11 +105,41 @@ impl<L: Renderer, R: Renderer> Renderer for TeeRenderer<L, R> {
         self.right.reset_attr()
     }
 
+    fn set_underline(&mut self, style: Underline) -> Result<()> {
+        self.left.set_underline(style)?;
+        self.right.set_underline(style)
+    }
+
+    fn set_underline_color(&mut self, color: Rgb) -> Result<()> {
+        self.left.set_underline_color(color)?;
+        self.right.set_underline_color(color)
+    }
+
+    fn reset_underline_color(&mut self) -> Result<()> {
+        self.left.reset_underline_color()?;
+        self.right.reset_underline_color()
+    }
+
     fn write(&mut self, char: char) -> Result<()> {
         self.left.write(char)?;
         self.right.write(char)
     }
 
+    fn write_grapheme(&mut self, grapheme: &str) -> Result<()> {
+        self.left.write_grapheme(grapheme)?;
+        self.right.write_grapheme(grapheme)
+    }
+
+    fn start_link(&mut self, uri: &str, id: Option<&str>) -> Result<()> {
+        self.left.start_link(uri, id)?;
+        self.right.start_link(uri, id)
+    }
+
+    fn end_link(&mut self) -> Result<()> {
+        self.left.end_link()?;
+        self.right.end_link()
+    }
+
     fn set_title(&mut self, title: &str) -> Result<()> {
         self.left.set_title(title)?;
         self.right.set_title(title)
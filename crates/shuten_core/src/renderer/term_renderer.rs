@@ -1,7 +1,9 @@
+use std::ops::Range;
+
 use crate::{
     geom::Pos2,
     renderer::Renderer,
-    style::{Attribute, Rgb},
+    style::{Attribute, Rgb, Underline},
 };
 
 /// A terminal [`Renderer`]
@@ -25,6 +27,24 @@ where
     }
 }
 
+impl<W> TermRenderer<W>
+where
+    W: std::io::Write,
+{
+    /// Write these codes as a single SGR sequence
+    fn write_sgr(&mut self, codes: impl IntoIterator<Item = u8>) -> std::io::Result<()> {
+        let mut sep = "\x1b[";
+        for code in codes {
+            self.out.write_fmt(format_args!("{sep}{code}"))?;
+            sep = ";";
+        }
+        if sep == ";" {
+            self.out.write_all(b"m")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg_attr(feature = "profiling", profiling::all_functions)]
 impl<W> Renderer for TermRenderer<W>
 where
@@ -40,7 +60,31 @@ where
     }
 
     fn clear_screen(&mut self) -> std::io::Result<()> {
-        self.out.write_all(b"\x1b[?2J")
+        self.out.write_all(b"\x1b[2J")
+    }
+
+    fn supports_scroll(&self) -> bool {
+        true
+    }
+
+    fn set_scroll_region(&mut self, rows: Range<u16>) -> std::io::Result<()> {
+        self.out.write_fmt(format_args!(
+            "\x1b[{top};{bottom}r",
+            top = rows.start + 1,
+            bottom = rows.end
+        ))
+    }
+
+    fn reset_scroll_region(&mut self) -> std::io::Result<()> {
+        self.out.write_all(b"\x1b[r")
+    }
+
+    fn scroll_up(&mut self, lines: u16) -> std::io::Result<()> {
+        self.out.write_fmt(format_args!("\x1b[{lines}S"))
+    }
+
+    fn scroll_down(&mut self, lines: u16) -> std::io::Result<()> {
+        self.out.write_fmt(format_args!("\x1b[{lines}T"))
     }
 
     #[inline]
@@ -63,29 +107,29 @@ where
 
     #[inline]
     fn set_attr(&mut self, attr: Attribute) -> std::io::Result<()> {
-        // TODO make this expansive by iterating from the lsb to the msb
-        if attr.is_bold() {
-            self.out.write_all(b"\x1b[1m")?
-        }
-        if attr.is_faint() {
-            self.out.write_all(b"\x1b[2m")?
-        }
-        if attr.is_italic() {
-            self.out.write_all(b"\x1b[3m")?
-        }
-        if attr.is_underline() {
-            self.out.write_all(b"\x1b[4m")?
-        }
-        if attr.is_blink() {
-            self.out.write_all(b"\x1b[5m")?
-        }
-        if attr.is_reverse() {
-            self.out.write_all(b"\x1b[7m")?
-        }
-        if attr.is_strike_out() {
-            self.out.write_all(b"\x1b[9m")?
-        }
-        Ok(())
+        self.write_sgr(
+            Attribute::CODES
+                .into_iter()
+                .filter(|&(flag, ..)| attr.intersects(flag))
+                .map(|(_, on, _)| on),
+        )
+    }
+
+    #[inline]
+    fn supports_unset_attr(&self) -> bool {
+        true
+    }
+
+    fn unset_attr(&mut self, attr: Attribute) -> std::io::Result<()> {
+        let mut codes = Attribute::CODES
+            .into_iter()
+            .filter(|&(flag, ..)| attr.intersects(flag))
+            .map(|(.., off)| off)
+            .collect::<Vec<_>>();
+        // attributes that share a code only need it once
+        codes.sort_unstable();
+        codes.dedup();
+        self.write_sgr(codes)
     }
 
     #[inline]
@@ -103,11 +147,46 @@ where
         self.out.write_all(b"\x1b[0m")
     }
 
+    #[inline]
+    fn set_underline(&mut self, style: Underline) -> std::io::Result<()> {
+        self.out
+            .write_fmt(format_args!("\x1b[4:{code}m", code = style.code()))
+    }
+
+    #[inline]
+    fn set_underline_color(&mut self, color: Rgb) -> std::io::Result<()> {
+        let Rgb(r, g, b) = color;
+        self.out.write_fmt(format_args!("\x1b[58;2;{r};{g};{b}m"))
+    }
+
+    #[inline]
+    fn reset_underline_color(&mut self) -> std::io::Result<()> {
+        self.out.write_all(b"\x1b[59m")
+    }
+
     #[inline]
     fn write(&mut self, char: char) -> std::io::Result<()> {
         self.out.write_all(char.encode_utf8(&mut [0; 4]).as_bytes())
     }
 
+    #[inline]
+    fn write_grapheme(&mut self, grapheme: &str) -> std::io::Result<()> {
+        self.out.write_all(grapheme.as_bytes())
+    }
+
+    fn start_link(&mut self, uri: &str, id: Option<&str>) -> std::io::Result<()> {
+        match id {
+            Some(id) => self
+                .out
+                .write_fmt(format_args!("\x1b]8;id={id};{uri}\x1b\\")),
+            None => self.out.write_fmt(format_args!("\x1b]8;;{uri}\x1b\\")),
+        }
+    }
+
+    fn end_link(&mut self) -> std::io::Result<()> {
+        self.out.write_all(b"\x1b]8;;\x1b\\")
+    }
+
     fn set_title(&mut self, title: &str) -> std::io::Result<()> {
         self.out.write_fmt(format_args!("\x1b]2;{title}\x07"))
     }
@@ -146,3 +225,31 @@ where
         self.out.write_all(b"\x1b[?7l")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geom::{rect, vec2},
+        Context,
+    };
+
+    #[test]
+    fn clear_screen_erases_the_display() {
+        let mut out = TermRenderer::new(Vec::new());
+        out.clear_screen().unwrap();
+        assert_eq!(out.out, b"\x1b[2J");
+    }
+
+    #[test]
+    fn resizing_clears_the_screen() {
+        let mut context = Context::new(rect(vec2(2, 1)));
+        context.resize(vec2(3, 1));
+
+        let mut out = TermRenderer::new(Vec::new());
+        context.end_frame(&mut out).unwrap();
+        let out = String::from_utf8(out.out).unwrap();
+        assert!(out.contains("\x1b[2J"), "{out:?}");
+        assert!(!out.contains("\x1b[?2J"), "{out:?}");
+    }
+}
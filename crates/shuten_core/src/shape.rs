@@ -0,0 +1,275 @@
+//! Shapes that are rasterized at cell resolution
+//!
+//! A cell is covered by a shape if its center is inside of the shape. Positions are in cells, `pos2f(0.5, 0.5)` is the center of the left-top cell
+//!
+//! Cells are roughly twice as tall as they are wide, so circles, arcs and rounded corners are squashed vertically by [`CELL_ASPECT`] to look round
+//!
+//! ```rust
+//! use shuten_core::geom::{pos2, pos2f, rect, vec2};
+//! use shuten_core::shape::Shape;
+//! use shuten_core::{Cell, Context};
+//!
+//! let mut context = Context::new(rect(vec2(20, 10)));
+//! let mut canvas = context.canvas();
+//!
+//! // this is 16 columns wide, but only 8 rows tall
+//! let circle = Shape::circle(pos2f(10.0, 5.0), 8.0);
+//! canvas.fill_shape(&circle, Cell::new('#'));
+//! canvas.stroke_shape(&circle, Cell::new('o'));
+//!
+//! let surface = context.surface();
+//! assert_eq!(surface[pos2(10, 5)].char, '#');
+//! assert_eq!(surface[pos2(2, 5)].char, 'o');
+//! assert_eq!(surface[pos2(10, 1)].char, 'o');
+//! assert_eq!(surface[pos2(10, 0)].char, ' ');
+//! ```
+use crate::geom::{pos2, pos2f, Pos2, Pos2f, Rect, Rectf};
+
+/// How many times taller a cell is than it is wide
+pub const CELL_ASPECT: f32 = 2.0;
+
+/// A shape that can be [filled](crate::Canvas::fill_shape) or [stroked](crate::Canvas::stroke_shape) on a [`Canvas`](crate::Canvas)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Shape {
+    /// A circle, the radius is in columns
+    Circle {
+        /// The center of the circle
+        center: Pos2f,
+        /// The radius, in columns
+        radius: f32,
+    },
+    /// An ellipse that fits inside of the [`Rectf`]
+    Ellipse(Rectf),
+    /// An arc of a circle
+    ///
+    /// Angles are in degrees, `0` points to the right and they go clockwise
+    ///
+    /// A stroked arc only draws the curve, a filled arc is a pie slice
+    Arc {
+        /// The center of the circle
+        center: Pos2f,
+        /// The radius, in columns
+        radius: f32,
+        /// Where the arc starts, in degrees
+        start: f32,
+        /// How far the arc sweeps, in degrees
+        sweep: f32,
+    },
+    /// A rectangle with rounded corners, the corner radius is in columns
+    RoundedRect {
+        /// The outer edges of the rectangle
+        rect: Rectf,
+        /// The radius of the corners, in columns
+        radius: f32,
+    },
+    /// A closed polygon through each of the points
+    ///
+    /// This uses the even-odd rule, so overlapping areas of a self-intersecting polygon are holes
+    Polygon(Vec<Pos2f>),
+}
+
+impl Shape {
+    /// Create a circle, the radius is in columns
+    pub const fn circle(center: Pos2f, radius: f32) -> Self {
+        Self::Circle { center, radius }
+    }
+
+    /// Create an ellipse that fits inside of the [`Rectf`]
+    pub const fn ellipse(rect: Rectf) -> Self {
+        Self::Ellipse(rect)
+    }
+
+    /// Create an arc, angles are in degrees
+    pub const fn arc(center: Pos2f, radius: f32, start: f32, sweep: f32) -> Self {
+        Self::Arc {
+            center,
+            radius,
+            start,
+            sweep,
+        }
+    }
+
+    /// Create a rectangle with rounded corners, the corner radius is in columns
+    pub const fn rounded_rect(rect: Rectf, radius: f32) -> Self {
+        Self::RoundedRect { rect, radius }
+    }
+
+    /// Create a closed polygon through each of the points
+    pub fn polygon(points: impl IntoIterator<Item = Pos2f>) -> Self {
+        Self::Polygon(points.into_iter().collect())
+    }
+
+    /// Get the bounding [`Rectf`] of this shape
+    pub fn bounds(&self) -> Rectf {
+        match self {
+            Self::Circle { center, radius } | Self::Arc { center, radius, .. } => {
+                let (x, y) = (radius.abs(), radius.abs() / CELL_ASPECT);
+                Rectf::from_min_max(
+                    pos2f(center.x - x, center.y - y),
+                    pos2f(center.x + x, center.y + y),
+                )
+            }
+            Self::Ellipse(rect) | Self::RoundedRect { rect, .. } => *rect,
+            Self::Polygon(points) => {
+                let Some(&first) = points.first() else {
+                    return Rectf::ZERO;
+                };
+                let (min, max) = points
+                    .iter()
+                    .fold((first, first), |(min, max), &p| (min.min(p), max.max(p)));
+                Rectf::from_min_max(min, max)
+            }
+        }
+    }
+
+    /// Is this position inside of this shape?
+    pub fn contains(&self, pos: Pos2f) -> bool {
+        match *self {
+            Self::Arc {
+                center,
+                start,
+                sweep,
+                ..
+            } => {
+                let angle = (pos.y - center.y) * CELL_ASPECT;
+                let angle = angle.atan2(pos.x - center.x).to_degrees();
+                self.interior(pos) && (sweep.abs() >= 360.0 || within(angle, start, sweep))
+            }
+            _ => self.interior(pos),
+        }
+    }
+
+    /// Get each cell that is covered by this shape
+    pub fn fill(&self) -> impl Iterator<Item = Pos2> + '_ {
+        self.fill_in(EVERYWHERE)
+    }
+
+    /// Get each cell on the outline of this shape
+    ///
+    /// These are the covered cells that have a neighbor outside of the shape
+    pub fn stroke(&self) -> impl Iterator<Item = Pos2> + '_ {
+        self.stroke_in(EVERYWHERE)
+    }
+
+    /// Get each cell that is covered by this shape, without leaving `clip`
+    pub(crate) fn fill_in(&self, clip: Rect) -> impl Iterator<Item = Pos2> + '_ {
+        self.cells(clip).filter(|&pos| self.contains(center(pos)))
+    }
+
+    /// Get each cell on the outline of this shape, without leaving `clip`
+    pub(crate) fn stroke_in(&self, clip: Rect) -> impl Iterator<Item = Pos2> + '_ {
+        self.fill_in(clip).filter(|&pos| {
+            let Pos2f { x, y } = center(pos);
+            [(-1.0, 0.0), (1.0, 0.0), (0.0, -1.0), (0.0, 1.0)]
+                .into_iter()
+                .any(|(dx, dy)| !self.interior(pos2f(x + dx, y + dy)))
+        })
+    }
+
+    /// Is this position inside of the closed outline of this shape?
+    ///
+    /// For arcs, this is the entire circle. So only the curve is stroked
+    fn interior(&self, pos: Pos2f) -> bool {
+        match self {
+            &Self::Circle { center, radius } | &Self::Arc { center, radius, .. } => {
+                let (dx, dy) = (pos.x - center.x, (pos.y - center.y) * CELL_ASPECT);
+                dx.hypot(dy) <= radius.abs()
+            }
+            Self::Ellipse(rect) => {
+                let (rx, ry) = (rect.width() * 0.5, rect.height() * 0.5);
+                let mid = rect.center();
+                let (dx, dy) = ((pos.x - mid.x) / rx, (pos.y - mid.y) / ry);
+                dx.hypot(dy) <= 1.0
+            }
+            &Self::RoundedRect { rect, radius } => {
+                let (hw, hh) = (rect.width() * 0.5, rect.height() * 0.5);
+                let mid = rect.center();
+                let (dx, dy) = ((pos.x - mid.x).abs(), (pos.y - mid.y).abs());
+                if dx > hw || dy > hh {
+                    return false;
+                }
+
+                let (rx, ry) = (radius.min(hw), (radius / CELL_ASPECT).min(hh));
+                let (qx, qy) = ((dx - (hw - rx)).max(0.0), (dy - (hh - ry)).max(0.0));
+                if rx <= 0.0 || ry <= 0.0 || qx == 0.0 || qy == 0.0 {
+                    return true;
+                }
+                (qx / rx).hypot(qy / ry) <= 1.0
+            }
+            Self::Polygon(points) => {
+                let mut inside = false;
+                let edges = points.iter().zip(points.iter().cycle().skip(1));
+                for (a, b) in edges {
+                    if (a.y > pos.y) != (b.y > pos.y)
+                        && pos.x < (b.x - a.x) * (pos.y - a.y) / (b.y - a.y) + a.x
+                    {
+                        inside = !inside
+                    }
+                }
+                inside
+            }
+        }
+    }
+
+    /// Get each cell in the bounds of this shape, that is also in `clip`
+    fn cells(&self, clip: Rect) -> impl Iterator<Item = Pos2> {
+        let bounds = self.bounds();
+        let clamp = |d: f32| d.clamp(0.0, u16::MAX as f32) as u16;
+        let left = clamp(bounds.left().floor()).max(clip.min.x);
+        let right = clamp(bounds.right().ceil()).min(clip.max.x);
+        let top = clamp(bounds.top().floor()).max(clip.min.y);
+        let bottom = clamp(bounds.bottom().ceil()).min(clip.max.y);
+        (top..bottom).flat_map(move |y| (left..right).map(move |x| pos2(x, y)))
+    }
+}
+
+/// Every position a cell can be at
+const EVERYWHERE: Rect = Rect::from_min_max(Pos2::ZERO, pos2(u16::MAX, u16::MAX));
+
+/// The center of the cell at this position
+fn center(pos: Pos2) -> Pos2f {
+    pos2f(f32::from(pos.x) + 0.5, f32::from(pos.y) + 0.5)
+}
+
+/// Is the `angle` between `start` and `start + sweep`? (in degrees)
+fn within(angle: f32, start: f32, sweep: f32) -> bool {
+    let (start, sweep) = match sweep < 0.0 {
+        true => (start + sweep, -sweep),
+        false => (start, sweep),
+    };
+    (angle - start).rem_euclid(360.0) <= sweep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geom::{rect, vec2},
+        Cell, Context,
+    };
+
+    #[test]
+    fn only_the_clip_is_rasterized() {
+        let huge = Shape::circle(pos2f(0.0, 0.0), 1_000_000.0);
+        let clip = Rect::from_min_size(pos2(2, 1), vec2(3, 2));
+        assert_eq!(huge.fill_in(clip).count(), 6);
+        assert_eq!(huge.stroke_in(clip).count(), 0);
+
+        let mut context = Context::new(rect(vec2(8, 4)));
+        context.canvas().fill_shape(&huge, Cell::new('#'));
+        context.canvas().stroke_shape(&huge, Cell::new('o'));
+        let surface = context.surface();
+        assert!(rect(vec2(8, 4))
+            .indices()
+            .all(|pos| surface[pos].char == '#'));
+    }
+
+    #[test]
+    fn clipped_stroke_matches_the_whole_stroke() {
+        let circle = Shape::circle(pos2f(10.0, 5.0), 8.0);
+        let clip = Rect::from_min_size(pos2(0, 0), vec2(10, 5));
+        let whole: Vec<_> = circle.stroke().filter(|&pos| clip.contains(pos)).collect();
+        assert_eq!(circle.stroke_in(clip).collect::<Vec<_>>(), whole);
+    }
+}
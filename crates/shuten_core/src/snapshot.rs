@@ -0,0 +1,729 @@
+//! Snapshot testing for [`Surface`]s
+//!
+//! A snapshot is a readable text fixture of a [`Surface`]:
+//! - a grid of its glyphs
+//! - optional `fg`, `bg`, `attr`, `underline`, `underline_color` and `link` layers, for the cells that differ from [`Cell::EMPTY`]
+//!
+//! Each layer is a grid with a key for each cell, followed by what each key means. A `.` is the default
+//!
+//! Control characters in the glyphs are escaped as `\u{..}`, and a backslash as `\\`
+//!
+//! ```text
+//! # 5x2
+//! [glyphs]
+//! |hello|
+//! |world|
+//!
+//! [fg]
+//! |aa...|
+//! |.....|
+//! a = #ff0000
+//!
+//! [attr]
+//! |bb...|
+//! |.....|
+//! b = Bold + Italic
+//! ```
+//!
+//! See [`assert_surface_snapshot!`](crate::assert_surface_snapshot) for using these in tests
+//!
+//! ```rust
+//! use shuten_core::{geom::{pos2, vec2}, snapshot, style::Attribute, Cell, Surface};
+//!
+//! let mut surface = Surface::new(vec2(5, 2));
+//! let mut canvas = surface.canvas();
+//! canvas.put(pos2(0, 0), Cell::new('h').fg(0xFF0000).attr(Attribute::BOLD));
+//! canvas.put(pos2(1, 0), Cell::new('i').fg(0xFF0000).attr(Attribute::BOLD));
+//!
+//! let fixture = snapshot::render(&surface);
+//! assert!(fixture.contains("|hi   |"));
+//!
+//! let parsed = snapshot::parse(&fixture).unwrap();
+//! assert_eq!(snapshot::diff(&parsed, &surface), None);
+//! ```
+use std::{
+    borrow::{Borrow, Cow},
+    collections::HashMap,
+    fmt::Write as _,
+    path::Path,
+};
+
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use crate::{
+    geom::{pos2, vec2, Pos2},
+    style::{Attribute, BlendMode, Color, Rgb, Rgba, Underline},
+    surface::{grapheme_width, CellAttr},
+    Cell, Hyperlink, Surface,
+};
+
+/// Setting this environment variable updates the snapshots, rather than comparing them
+pub const UPDATE_VAR: &str = "SHUTEN_UPDATE_SNAPSHOTS";
+
+/// How many differences are listed, before they are summarized
+const MAX_DIFFERENCES: usize = 32;
+
+/// Assert that a [`Surface`] matches its snapshot
+///
+/// The snapshot is stored in `tests/snapshots/<name>.snap`, relative to the crate being tested
+///
+/// - If the snapshot doesn't exist, it is written and the assertion passes
+/// - If [`SHUTEN_UPDATE_SNAPSHOTS`](crate::snapshot::UPDATE_VAR) is set, the snapshot is overwritten
+/// - Otherwise, a cell-by-cell diff is printed if it doesn't match
+///
+/// This accepts a [`Surface`], a reference to one, or the [frame](crate::Context::frame) of a [`Context`](crate::Context)
+///
+/// ```rust,no_run
+/// use shuten_core::{assert_surface_snapshot, geom::{pos2, rect, vec2}, Cell, Context};
+///
+/// let mut context = Context::new(rect(vec2(10, 2)));
+/// context.canvas().put(pos2(0, 0), Cell::new('@'));
+/// assert_surface_snapshot!("at_sign", context.frame());
+/// ```
+#[macro_export]
+macro_rules! assert_surface_snapshot {
+    ($name:expr, $surface:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.snap", $name)),
+            $surface,
+        )
+    };
+}
+
+/// Assert that a [`Surface`] matches the snapshot at this path
+///
+/// See [`assert_surface_snapshot!`](crate::assert_surface_snapshot)
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, surface: impl Borrow<Surface>) {
+    let (path, surface) = (path.as_ref(), surface.borrow());
+
+    let update = std::env::var_os(UPDATE_VAR).is_some_and(|var| !var.is_empty());
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create snapshot directory");
+        }
+        std::fs::write(path, render(surface)).expect("write snapshot");
+        eprintln!("wrote snapshot: {}", path.display());
+        return;
+    }
+
+    let fixture = std::fs::read_to_string(path).expect("read snapshot");
+    let expected = match parse(&fixture) {
+        Ok(expected) => expected,
+        Err(err) => panic!("invalid snapshot {}: {err}", path.display()),
+    };
+
+    if let Some(diff) = diff(&expected, surface) {
+        panic!(
+            "snapshot {} does not match\n\n{diff}\nset {UPDATE_VAR}=1 to update it",
+            path.display()
+        );
+    }
+}
+
+/// Render a [`Surface`] as a snapshot
+#[cfg_attr(feature = "profiling", profiling::function)]
+pub fn render(surface: &Surface) -> String {
+    let size = surface.size();
+    let mut out = String::new();
+    _ = writeln!(out, "# {}x{}", size.x, size.y);
+
+    out.push_str("[glyphs]\n");
+    out.push_str(&glyphs(surface));
+
+    for layer in Layer::ALL {
+        let default = layer.get(surface, &Cell::EMPTY);
+        let mut keys = Keys::default();
+        let mut grid = String::new();
+
+        for y in 0..size.y {
+            grid.push('|');
+            for x in 0..size.x {
+                match layer.get(surface, &surface[pos2(x, y)]) {
+                    value if value == default => grid.push('.'),
+                    value => grid.push(keys.get(value)),
+                }
+            }
+            grid.push_str("|\n");
+        }
+
+        if keys.values.is_empty() {
+            continue;
+        }
+
+        _ = write!(out, "\n[{}]\n{grid}", layer.name());
+        for (key, value) in keys.values {
+            _ = writeln!(out, "{key} = {value}");
+        }
+    }
+    out
+}
+
+/// Parse a snapshot into a [`Surface`]
+///
+/// Errors are reported with the line they occurred on
+#[cfg_attr(feature = "profiling", profiling::function)]
+pub fn parse(input: &str) -> Result<Surface, String> {
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+    let error = |line: usize, err: &dyn std::fmt::Display| format!("line {line}: {err}");
+
+    let (n, header) = lines.next().ok_or("empty snapshot")?;
+    let (w, h) = header
+        .strip_prefix("# ")
+        .and_then(|size| size.split_once('x'))
+        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+        .ok_or_else(|| error(n, &"expected a `# WxH` header"))?;
+
+    let mut surface = Surface::new(vec2(w, h));
+    let mut section = None;
+    let mut grid = Vec::new();
+
+    let lines = lines.chain(std::iter::once((0, "")));
+    for (n, line) in lines {
+        if let Some(row) = line.strip_prefix('|') {
+            let row = row
+                .strip_suffix('|')
+                .ok_or_else(|| error(n, &"expected a `|` at the end of the row"))?;
+            grid.push((n, row));
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(" = ") {
+            let Some(Section::Layer(layer, legend)) = &mut section else {
+                return Err(error(n, &"a key can only follow a layer"));
+            };
+            let mut key = key.chars();
+            let (Some(key), None) = (key.next(), key.next()) else {
+                return Err(error(n, &"a key must be a single character"));
+            };
+            let value = layer.parse(value).map_err(|err| error(n, &err))?;
+            legend.insert(key, value);
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with('[') {
+            if let Some(section) = section.take() {
+                section.apply(&mut surface, &grid)?;
+            }
+            grid.clear();
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            section = Some(match name {
+                "glyphs" => Section::Glyphs,
+                name => Layer::ALL
+                    .into_iter()
+                    .find(|layer| layer.name() == name)
+                    .map(|layer| Section::Layer(layer, HashMap::new()))
+                    .ok_or_else(|| error(n, &format!("unknown section: {name}")))?,
+            });
+        } else if !line.is_empty() {
+            return Err(error(n, &format!("unexpected line: {line}")));
+        }
+    }
+
+    Ok(surface)
+}
+
+/// Compare two [`Surface`]s cell-by-cell, producing a readable diff if they are different
+pub fn diff(expected: &Surface, actual: &Surface) -> Option<String> {
+    let mut out = String::new();
+    if expected.size() != actual.size() {
+        let (e, a) = (expected.size(), actual.size());
+        _ = writeln!(
+            out,
+            "size: expected {}x{}, actual {}x{}",
+            e.x, e.y, a.x, a.y
+        );
+    } else {
+        let size = expected.size();
+        let mut differences = 0;
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let pos = pos2(x, y);
+                let Some(difference) = describe(pos, expected, actual) else {
+                    continue;
+                };
+                if differences < MAX_DIFFERENCES {
+                    _ = writeln!(out, "{difference}");
+                }
+                differences += 1;
+            }
+        }
+
+        if differences == 0 {
+            return None;
+        }
+        if differences > MAX_DIFFERENCES {
+            _ = writeln!(out, "... and {} more", differences - MAX_DIFFERENCES);
+        }
+    }
+
+    _ = write!(
+        out,
+        "\nexpected:\n{}\nactual:\n{}",
+        glyphs(expected),
+        glyphs(actual)
+    );
+    Some(out)
+}
+
+/// Describe how the cell at this position is different, if it is
+fn describe(pos: Pos2, expected: &Surface, actual: &Surface) -> Option<String> {
+    let (left, right) = (expected[pos], actual[pos]);
+    let text = |surface: &Surface, cell: &Cell| match surface.grapheme(cell) {
+        Some(grapheme) => format!("{grapheme:?}"),
+        None => format!("{:?}", cell.char),
+    };
+
+    let mut fields = vec![];
+    let (l, r) = (text(expected, &left), text(actual, &right));
+    if l != r {
+        fields.push(format!("char: {l} != {r}"));
+    }
+    for layer in Layer::ALL {
+        let (l, r) = (layer.get(expected, &left), layer.get(actual, &right));
+        if l != r {
+            fields.push(format!("{}: {l} != {r}", layer.name()));
+        }
+    }
+
+    (!fields.is_empty()).then(|| format!("({}, {}) {}", pos.x, pos.y, fields.join(", ")))
+}
+
+/// The glyph grid of a surface, wide cells occupy two columns
+fn glyphs(surface: &Surface) -> String {
+    let size = surface.size();
+    let mut out = String::new();
+    for y in 0..size.y {
+        out.push('|');
+        let mut skip = false;
+        for x in 0..size.x {
+            let cell = surface[pos2(x, y)];
+            if std::mem::take(&mut skip) && cell.is_continuation() {
+                continue;
+            }
+            match surface.grapheme(&cell) {
+                Some(grapheme) => escape(&mut out, grapheme),
+                None if cell.is_continuation() => out.push(' '),
+                None => escape(&mut out, cell.char.encode_utf8(&mut [0; 4])),
+            }
+            skip = cell.width() > 1;
+        }
+        out.push_str("|\n");
+    }
+    out
+}
+
+/// Write a glyph, escaping control characters and backslashes
+fn escape(out: &mut String, glyph: &str) {
+    for c in glyph.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => _ = write!(out, "\\u{{{:x}}}", u32::from(c)),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Split a row of glyphs into its graphemes, an escaped character is a grapheme on its own
+fn unescape(row: &str) -> Result<Vec<Cow<'_, str>>, String> {
+    let mut graphemes = Vec::new();
+    let mut rest = row;
+    while let Some(start) = rest.find('\\') {
+        graphemes.extend(rest[..start].graphemes(true).map(Cow::Borrowed));
+
+        let tail = &rest[start + 1..];
+        let (c, len) = match tail.strip_prefix("u{").and_then(|s| s.split_once('}')) {
+            _ if tail.starts_with('\\') => ('\\', 1),
+            Some((hex, _)) => {
+                let c = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape: \\u{{{hex}}}"))?;
+                (c, hex.len() + 3)
+            }
+            None => return Err(String::from("expected `\\\\` or `\\u{..}`")),
+        };
+        graphemes.push(Cow::Owned(c.to_string()));
+        rest = &tail[len..];
+    }
+    graphemes.extend(rest.graphemes(true).map(Cow::Borrowed));
+    Ok(graphemes)
+}
+
+enum Section {
+    Glyphs,
+    Layer(Layer, HashMap<char, String>),
+}
+
+impl Section {
+    fn apply(self, surface: &mut Surface, grid: &[(usize, &str)]) -> Result<(), String> {
+        let size = surface.size();
+        if grid.len() != size.y as usize {
+            let n = grid.first().map_or(0, |&(n, _)| n);
+            return Err(format!(
+                "line {n}: expected {} rows, got {}",
+                size.y,
+                grid.len()
+            ));
+        }
+
+        for (y, &(n, row)) in (0..).zip(grid) {
+            match &self {
+                Self::Glyphs => {
+                    let mut x = 0;
+                    let graphemes = unescape(row).map_err(|err| format!("line {n}: {err}"))?;
+                    for grapheme in &graphemes {
+                        if x >= size.x {
+                            return Err(format!("line {n}: row is wider than {}", size.x));
+                        }
+                        let mut chars = grapheme.chars();
+                        let first = chars.next().unwrap_or(' ');
+                        let mut cell = surface[pos2(x, y)].char(first);
+                        if chars.next().is_some() {
+                            cell.grapheme = Some(surface.intern(grapheme));
+                        }
+                        surface[pos2(x, y)] = cell;
+
+                        let width = grapheme_width(grapheme);
+                        if width > 1 && x + 1 < size.x {
+                            let next = &mut surface[pos2(x + 1, y)];
+                            *next = next.char(Cell::CONTINUATION.char);
+                        }
+                        x += width;
+                    }
+                }
+                Self::Layer(layer, legend) => {
+                    if row.chars().count() != size.x as usize {
+                        return Err(format!("line {n}: expected {} columns", size.x));
+                    }
+                    for (x, key) in (0..).zip(row.chars()) {
+                        if key == '.' {
+                            continue;
+                        }
+                        let value = legend
+                            .get(&key)
+                            .ok_or_else(|| format!("line {n}: unknown key: {key}"))?;
+                        layer
+                            .set(surface, pos2(x, y), value)
+                            .map_err(|err| format!("line {n}: {err}"))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A style layer of a snapshot
+#[derive(Copy, Clone)]
+enum Layer {
+    Fg,
+    Bg,
+    Attr,
+    Underline,
+    UnderlineColor,
+    Link,
+}
+
+impl Layer {
+    const ALL: [Self; 6] = [
+        Self::Fg,
+        Self::Bg,
+        Self::Attr,
+        Self::Underline,
+        Self::UnderlineColor,
+        Self::Link,
+    ];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Fg => "fg",
+            Self::Bg => "bg",
+            Self::Attr => "attr",
+            Self::Underline => "underline",
+            Self::UnderlineColor => "underline_color",
+            Self::Link => "link",
+        }
+    }
+
+    fn get(self, surface: &Surface, cell: &Cell) -> String {
+        match self {
+            Self::Fg => color_to_string(cell.fg),
+            Self::Bg => color_to_string(cell.bg),
+            Self::Attr => match cell.attr {
+                CellAttr::Attr(attr) => format!("{attr:?}"),
+                CellAttr::Reset => String::from("Reset"),
+                CellAttr::Merge { add, remove } => format!("merge add={add:?} remove={remove:?}"),
+            },
+            Self::Underline => format!("{:?}", cell.underline),
+            Self::UnderlineColor => color_to_string(cell.underline_color),
+            Self::Link => match surface.link(cell) {
+                Some(Hyperlink { uri, id: Some(id) }) => format!("{uri} id={id}"),
+                Some(Hyperlink { uri, id: None }) => uri.to_string(),
+                None => String::from("none"),
+            },
+        }
+    }
+
+    /// Validate a value, normalizing it
+    fn parse(self, value: &str) -> Result<String, String> {
+        let mut surface = Surface::new(vec2(1, 1));
+        self.set(&mut surface, Pos2::ZERO, value)?;
+        Ok(self.get(&surface, &surface[Pos2::ZERO]))
+    }
+
+    fn set(self, surface: &mut Surface, pos: Pos2, value: &str) -> Result<(), String> {
+        match self {
+            Self::Fg => surface[pos].fg = color_from_str(value)?,
+            Self::Bg => surface[pos].bg = color_from_str(value)?,
+            Self::Attr => surface[pos].attr = attr_from_str(value)?,
+            Self::Underline => {
+                surface[pos].underline = [
+                    Underline::Single,
+                    Underline::Double,
+                    Underline::Curly,
+                    Underline::Dotted,
+                    Underline::Dashed,
+                ]
+                .into_iter()
+                .find(|style| format!("{style:?}") == value)
+                .ok_or_else(|| format!("unknown underline: {value}"))?;
+            }
+            Self::UnderlineColor => surface[pos].underline_color = color_from_str(value)?,
+            Self::Link => {
+                surface[pos].link = match value.split_once(" id=") {
+                    _ if value == "none" => None,
+                    Some((uri, id)) => Some(surface.intern_link(uri, Some(id))),
+                    None => Some(surface.intern_link(value, None)),
+                };
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Assigns keys to the values of a layer, in the order they are seen
+#[derive(Default)]
+struct Keys {
+    values: Vec<(char, String)>,
+}
+
+impl Keys {
+    fn get(&mut self, value: String) -> char {
+        if let Some((key, _)) = self.values.iter().find(|(_, v)| *v == value) {
+            return *key;
+        }
+
+        const ASCII: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        // after ascii, single width letters are used
+        let key =
+            ASCII
+                .chars()
+                .chain(('\u{C0}'..).filter(|c| {
+                    c.is_alphabetic() && grapheme_width(c.encode_utf8(&mut [0; 4])) == 1
+                }))
+                .nth(self.values.len())
+                .expect("too many unique values for a snapshot");
+        self.values.push((key, value));
+        key
+    }
+}
+
+fn color_to_string(color: Color) -> String {
+    match color {
+        Color::Rgb(rgb) => format!("{rgb:x}"),
+        Color::Blend(Rgba(r, g, b, a), mode) => {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x} {mode:?}")
+        }
+        Color::Reset => String::from("reset"),
+        Color::Reuse => String::from("reuse"),
+    }
+}
+
+fn color_from_str(input: &str) -> Result<Color, String> {
+    match input {
+        "reset" => return Ok(Color::Reset),
+        "reuse" => return Ok(Color::Reuse),
+        _ => {}
+    }
+
+    if let Some((rgba, mode)) = input.split_once(' ') {
+        let mode = [
+            BlendMode::Normal,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Additive,
+        ]
+        .into_iter()
+        .find(|m| format!("{m:?}") == mode)
+        .ok_or_else(|| format!("unknown blend mode: {mode}"))?;
+
+        let rgba = rgba
+            .strip_prefix('#')
+            .filter(|s| s.len() == 8)
+            .and_then(|s| u32::from_str_radix(s, 16).ok())
+            .ok_or_else(|| format!("invalid rgba color: {rgba}"))?;
+        return Ok(Color::Blend(Rgba::from_u32(rgba), mode));
+    }
+
+    input
+        .parse::<Rgb>()
+        .map(Color::Rgb)
+        .map_err(|err| format!("{err}: {input}"))
+}
+
+fn attr_from_str(input: &str) -> Result<CellAttr, String> {
+    let flags = |input: &str| match input {
+        "Reset" => Ok(Attribute::default()),
+        input => input.parse::<Attribute>(),
+    };
+
+    if input == "Reset" {
+        return Ok(CellAttr::Reset);
+    }
+
+    let Some(merge) = input.strip_prefix("merge ") else {
+        return flags(input).map(CellAttr::Attr);
+    };
+    let (add, remove) = merge
+        .strip_prefix("add=")
+        .and_then(|merge| merge.split_once(" remove="))
+        .ok_or_else(|| format!("invalid merge: {input}"))?;
+    Ok(CellAttr::Merge {
+        add: flags(add)?,
+        remove: flags(remove)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Attribute;
+
+    fn round_trip(surface: &Surface) -> String {
+        let fixture = render(surface);
+        let parsed = parse(&fixture).unwrap_or_else(|err| panic!("{err}\n{fixture}"));
+        assert_eq!(diff(&parsed, surface), None, "{fixture}");
+        fixture
+    }
+
+    #[test]
+    fn styles_round_trip() {
+        let mut surface = Surface::new(vec2(6, 2));
+        let mut canvas = surface.canvas();
+        let link = canvas.link("https://example.com", Some("a"));
+        canvas.put(pos2(0, 0), Cell::new('a').fg(0xFF0000).bg(0x000080));
+        canvas.put(
+            pos2(1, 0),
+            Cell::new('b').attr(Attribute::BOLD | Attribute::ITALIC),
+        );
+        canvas.put(pos2(2, 0), Cell::new('c').underline(Underline::Curly));
+        canvas.put(pos2(3, 0), Cell::new('d').link(link));
+        canvas.put(pos2(0, 1), Cell::new('e').fg(Color::Reset).bg(Color::Reuse));
+
+        let fixture = round_trip(&surface);
+        assert!(fixture.starts_with("# 6x2\n[glyphs]\n|abcd  |\n|e     |\n"));
+    }
+
+    #[test]
+    fn graphemes_round_trip() {
+        let mut surface = Surface::new(vec2(6, 1));
+        let mut canvas = surface.canvas();
+        canvas.put_grapheme(pos2(0, 0), "漢", Cell::EMPTY);
+        canvas.put_grapheme(pos2(2, 0), "e\u{301}", Cell::EMPTY);
+        canvas.put_grapheme(pos2(3, 0), "🇯🇵", Cell::EMPTY);
+
+        let fixture = round_trip(&surface);
+        assert!(fixture.contains("|漢e\u{301}🇯🇵 |"));
+    }
+
+    #[test]
+    fn control_characters_are_escaped() {
+        let mut surface = Surface::new(vec2(5, 1));
+        let mut canvas = surface.canvas();
+        canvas.put(pos2(0, 0), Cell::new('\x1b'));
+        canvas.put(pos2(1, 0), Cell::new('\\'));
+        canvas.put(pos2(2, 0), Cell::new('\t'));
+        canvas.put(pos2(3, 0), Cell::new('u'));
+
+        let fixture = round_trip(&surface);
+        assert!(fixture.contains("|\\u{1b}\\\\\\u{9}u |"), "{fixture}");
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        let parse = |row: &str| parse(&format!("# 2x1\n[glyphs]\n|{row}|\n"));
+        assert_eq!(parse("\\u{41}b").unwrap()[pos2(0, 0)].char, 'A');
+        assert!(parse("\\x").unwrap_err().starts_with("line 3:"));
+        assert!(parse("\\u{d800}").is_err());
+        assert!(parse("\\u{41").is_err());
+        assert!(parse("\\").is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |input: &str| parse(input).unwrap_err();
+
+        assert_eq!(error(""), "empty snapshot");
+        assert_eq!(error("2x1"), "line 1: expected a `# WxH` header");
+        assert_eq!(
+            error("# 2x1\n[glyphs]\n|ab\n"),
+            "line 3: expected a `|` at the end of the row"
+        );
+        assert_eq!(
+            error("# 2x2\n[glyphs]\n|ab|\n"),
+            "line 3: expected 2 rows, got 1"
+        );
+        assert_eq!(
+            error("# 2x1\n[glyphs]\n|abc|\n"),
+            "line 3: row is wider than 2"
+        );
+        assert_eq!(
+            error("# 2x1\n[colors]\n"),
+            "line 2: unknown section: colors"
+        );
+        assert_eq!(
+            error("# 2x1\na = #fff\n"),
+            "line 2: a key can only follow a layer"
+        );
+        assert_eq!(
+            error("# 2x1\n[fg]\n|a.|\nab = #ffffff\n"),
+            "line 4: a key must be a single character"
+        );
+        assert_eq!(
+            error("# 2x1\n[fg]\n|b.|\na = #ffffff\n"),
+            "line 3: unknown key: b"
+        );
+        assert_eq!(
+            error("# 2x1\n[fg]\n|a|\na = #ffffff\n"),
+            "line 3: expected 2 columns"
+        );
+        assert_eq!(
+            error("# 2x1\n[underline]\n|a.|\na = Wavy\n"),
+            "line 4: unknown underline: Wavy"
+        );
+        assert_eq!(error("# 2x1\nhello\n"), "line 2: unexpected line: hello");
+    }
+
+    #[test]
+    fn diff_lists_the_differences() {
+        let mut expected = Surface::new(vec2(3, 1));
+        expected.canvas().put(pos2(1, 0), Cell::new('x'));
+        let mut actual = expected.clone();
+        actual.canvas().put(pos2(1, 0), Cell::new('y').fg(0x00FF00));
+
+        let diff = diff(&expected, &actual).unwrap();
+        assert!(
+            diff.starts_with("(1, 0) char: 'x' != 'y', fg: reset != #00ff00\n"),
+            "{diff}"
+        );
+
+        let diff = super::diff(&Surface::new(vec2(1, 1)), &Surface::new(vec2(2, 1))).unwrap();
+        assert!(diff.starts_with("size: expected 1x1, actual 2x1\n"));
+    }
+}
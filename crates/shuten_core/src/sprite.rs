@@ -0,0 +1,400 @@
+//! Reusable, animated, grids of cells
+//!
+//! A [`Sprite`] has one or more frames of the same size. [Transparent](Cell::is_transparent) cells aren't drawn, so a sprite doesn't have to be rectangular
+//!
+//! ```rust
+//! use shuten_core::geom::{offset, pos2, rect, vec2};
+//! use shuten_core::sprite::Sprite;
+//! use shuten_core::Context;
+//!
+//! let ship: Sprite = "
+//! anchor = 1,1
+//!
+//! [frame]
+//! | ^ |
+//! |<#>|
+//! [keys]
+//! | w |
+//! |rwr|
+//!
+//! [palette]
+//! w = #ffffff
+//! r = #ff0000 on #000000
+//! "
+//! .parse()
+//! .unwrap();
+//!
+//! let mut context = Context::new(rect(vec2(10, 5)));
+//! // the anchor is drawn at the offset, the rest of the sprite slides off of the left edge
+//! ship.draw(&mut context.canvas(), 0, offset(0, 2));
+//!
+//! let surface = context.surface();
+//! assert_eq!(surface[pos2(0, 1)].char, '^');
+//! assert_eq!(surface[pos2(0, 2)].char, '#');
+//! assert_eq!(surface[pos2(1, 2)].char, '>');
+//! ```
+//!
+//! ## Format
+//!
+//! - `anchor = x,y` sets the [anchor](Sprite::anchor), this is optional but it must be inside of the sprite
+//! - each `[frame]` is a grid of glyphs, with each row between `|`
+//! - it's followed by a `[keys]` grid, with a key for each column
+//!   - a space is a transparent cell
+//!   - anything else is looked up in the palette
+//! - the `[palette]` has a `k = fg` or `k = fg on bg` line for each key, the colors are [`Rgb`]s
+use std::{collections::HashMap, str::FromStr};
+
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use crate::{
+    geom::{offset, pos2, vec2, Offset, Pos2, Vec2},
+    style::{Color, Rgb},
+    surface::grapheme_width,
+    Canvas, Cell, Surface,
+};
+
+/// A grid of [`Cell`]s, with a transparency mask, animation frames and an anchor point
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sprite {
+    frames: Vec<Surface>,
+    anchor: Pos2,
+}
+
+impl Sprite {
+    /// Create a new sprite with a single, fully transparent, frame
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            frames: vec![transparent(size)],
+            anchor: Pos2::ZERO,
+        }
+    }
+
+    /// Create a new sprite from a [`Surface`], as its first frame
+    pub fn from_surface(surface: Surface) -> Self {
+        Self {
+            frames: vec![surface],
+            anchor: Pos2::ZERO,
+        }
+    }
+
+    /// The size of each frame
+    pub fn size(&self) -> Vec2 {
+        self.frames[0].size()
+    }
+
+    /// The anchor (or pivot point), in the sprite
+    ///
+    /// This is the position that is placed at the offset when its [drawn](Sprite::draw)
+    pub const fn anchor(&self) -> Pos2 {
+        self.anchor
+    }
+
+    /// Set the anchor (or pivot point), in the sprite
+    pub fn set_anchor(&mut self, anchor: Pos2) {
+        self.anchor = anchor
+    }
+
+    /// Set the anchor (or pivot point), in the sprite
+    pub fn with_anchor(mut self, anchor: Pos2) -> Self {
+        self.set_anchor(anchor);
+        self
+    }
+
+    /// How many frames this sprite has
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Get a frame, this wraps around the [number of frames](Sprite::frame_count)
+    pub fn frame(&self, frame: usize) -> &Surface {
+        &self.frames[frame % self.frames.len()]
+    }
+
+    /// Get a [`Canvas`] to draw on a frame, this wraps around the [number of frames](Sprite::frame_count)
+    pub fn frame_canvas(&mut self, frame: usize) -> Canvas<'_> {
+        let len = self.frames.len();
+        self.frames[frame % len].canvas()
+    }
+
+    /// Add a new, fully transparent, frame. This returns its index
+    pub fn add_frame(&mut self) -> usize {
+        self.push_frame(transparent(self.size()))
+    }
+
+    /// Add a [`Surface`] as a new frame. This returns its index
+    ///
+    /// # Panics
+    /// If the surface isn't the same size as the other frames
+    #[track_caller]
+    pub fn push_frame(&mut self, surface: Surface) -> usize {
+        assert_eq!(
+            surface.size(),
+            self.size(),
+            "every frame must be the same size"
+        );
+        self.frames.push(surface);
+        self.frames.len() - 1
+    }
+
+    /// Is the [`Cell`] at this position in the frame drawn?
+    pub fn is_opaque(&self, frame: usize, pos: Pos2) -> bool {
+        let frame = self.frame(frame);
+        frame.contains(pos) && !frame[pos].is_transparent()
+    }
+
+    /// Draw a frame onto the [`Canvas`], with its anchor at `at`
+    ///
+    /// This can be negative, anything outside of the canvas is clipped
+    pub fn draw(&self, canvas: &mut Canvas, frame: usize, at: Offset) {
+        self.draw_with(canvas, frame, at, |color| color)
+    }
+
+    /// Draw a frame onto the [`Canvas`], with its anchor at `at`, tinting its colors
+    ///
+    /// Each [`Rgb`] color is [blended](Rgb::blend) with the `tint`
+    pub fn draw_tinted(&self, canvas: &mut Canvas, frame: usize, at: Offset, tint: Rgb, mix: f32) {
+        self.draw_with(canvas, frame, at, |color| match color {
+            Color::Rgb(color) => Color::Rgb(color.blend(tint, mix)),
+            color => color,
+        })
+    }
+
+    fn draw_with(
+        &self,
+        canvas: &mut Canvas,
+        frame: usize,
+        at: Offset,
+        color: impl Fn(Color) -> Color,
+    ) {
+        let frame = self.frame(frame);
+        let at = offset(
+            at.x.saturating_sub(i32::from(self.anchor.x)),
+            at.y.saturating_sub(i32::from(self.anchor.y)),
+        );
+        canvas.blit_with(frame, frame.rect(), at, |cell| Cell {
+            fg: color(cell.fg),
+            bg: color(cell.bg),
+            underline_color: color(cell.underline_color),
+            ..cell
+        })
+    }
+}
+
+impl FromStr for Sprite {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = |line: usize, err: &dyn std::fmt::Display| format!("line {line}: {err}");
+
+        let mut anchor = (0, Pos2::ZERO);
+        let mut palette = HashMap::new();
+        let mut frames = Vec::<(Vec<_>, Vec<_>)>::new();
+        let mut section = "";
+
+        for (n, line) in (1..).zip(input.lines()) {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                section = match name {
+                    "frame" => {
+                        frames.push(Default::default());
+                        "frame"
+                    }
+                    "keys" if !frames.is_empty() => "keys",
+                    "keys" => return Err(error(n, &"keys must follow a frame")),
+                    "palette" => "palette",
+                    name => return Err(error(n, &format!("unknown section: {name}"))),
+                };
+                continue;
+            }
+
+            if let Some(row) = line.strip_prefix('|') {
+                let row = row
+                    .strip_suffix('|')
+                    .ok_or_else(|| error(n, &"expected a `|` at the end of the row"))?;
+                match (section, frames.last_mut()) {
+                    ("frame", Some((glyphs, _))) => glyphs.push((n, row)),
+                    ("keys", Some((_, keys))) => keys.push((n, row)),
+                    _ => return Err(error(n, &"a row can only be in a frame or keys")),
+                }
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(" = ") else {
+                return Err(error(n, &format!("unexpected line: {line}")));
+            };
+
+            if section.is_empty() && key == "anchor" {
+                let pos = value
+                    .split_once(',')
+                    .and_then(|(x, y)| Some(pos2(x.trim().parse().ok()?, y.trim().parse().ok()?)))
+                    .ok_or_else(|| error(n, &"expected an `anchor = x,y`"))?;
+                anchor = (n, pos);
+                continue;
+            }
+
+            if section != "palette" {
+                return Err(error(n, &"a key can only be in the palette"));
+            }
+            let mut chars = key.chars();
+            let (Some(key), None) = (chars.next(), chars.next()) else {
+                return Err(error(n, &"a key must be a single character"));
+            };
+
+            let (fg, bg) = match value.split_once(" on ") {
+                Some((fg, bg)) => (fg, Some(bg)),
+                None => (value, None),
+            };
+            let fg = Color::Rgb(fg.trim().parse().map_err(|err| error(n, &err))?);
+            let bg = match bg {
+                Some(bg) => Color::Rgb(bg.trim().parse().map_err(|err| error(n, &err))?),
+                None => Color::Reuse,
+            };
+            palette.insert(key, (fg, bg));
+        }
+
+        let mut sprite = None::<Self>;
+        for (glyphs, keys) in frames {
+            let frame = parse_frame(&glyphs, &keys, &palette)?;
+            match &mut sprite {
+                Some(sprite) if sprite.size() != frame.size() => {
+                    let n = glyphs.first().map_or(0, |&(n, _)| n);
+                    return Err(error(n, &"every frame must be the same size"));
+                }
+                Some(sprite) => _ = sprite.push_frame(frame),
+                None => sprite = Some(Self::from_surface(frame)),
+            }
+        }
+
+        let sprite = sprite.ok_or("a sprite needs at least one frame")?;
+        let (n, anchor) = anchor;
+        if !sprite.frame(0).contains(anchor) {
+            let size = sprite.size();
+            let err = format!("the anchor is outside of the {}x{} sprite", size.x, size.y);
+            return Err(error(n, &err));
+        }
+        Ok(sprite.with_anchor(anchor))
+    }
+}
+
+fn parse_frame(
+    glyphs: &[(usize, &str)],
+    keys: &[(usize, &str)],
+    palette: &HashMap<char, (Color, Color)>,
+) -> Result<Surface, String> {
+    let error = |line: usize, err: &dyn std::fmt::Display| format!("line {line}: {err}");
+
+    let width = |row: &str| row.graphemes(true).map(grapheme_width).sum();
+    let w = glyphs.iter().map(|&(_, row)| width(row)).max().unwrap_or(0);
+    let size = vec2(w, glyphs.len() as u16);
+
+    if keys.len() != glyphs.len() {
+        let n = keys.first().or(glyphs.first()).map_or(0, |&(n, _)| n);
+        return Err(error(n, &format!("expected {} rows of keys", glyphs.len())));
+    }
+
+    let mut surface = transparent(size);
+    for (y, (&(_, glyphs), &(n, keys))) in (0..).zip(glyphs.iter().zip(keys)) {
+        if keys.chars().count() != w as usize {
+            return Err(error(n, &format!("expected {w} keys")));
+        }
+
+        let mut x = 0;
+        for grapheme in glyphs.graphemes(true) {
+            surface
+                .canvas()
+                .put_grapheme(pos2(x, y), grapheme, Cell::TRANSPARENT);
+            x += grapheme_width(grapheme);
+        }
+
+        for (x, key) in (0..).zip(keys.chars()) {
+            if key == ' ' {
+                continue;
+            }
+            let &(fg, bg) = palette
+                .get(&key)
+                .ok_or_else(|| error(n, &format!("unknown key: {key}")))?;
+            let cell = &mut surface[pos2(x, y)];
+            (cell.fg, cell.bg) = (fg, bg);
+        }
+    }
+    Ok(surface)
+}
+
+fn transparent(size: Vec2) -> Surface {
+    let mut surface = Surface::new(size);
+    surface.fill(Cell::TRANSPARENT);
+    surface
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geom::rect, Context};
+
+    const SHIP: &str = "
+[frame]
+|<>|
+[keys]
+|ab|
+[palette]
+a = #ff0000
+b = #00ff00
+";
+
+    #[test]
+    fn anchor_must_be_inside() {
+        let sprite: Sprite = format!("anchor = 1,0\n{SHIP}").parse().unwrap();
+        assert_eq!(sprite.anchor(), pos2(1, 0));
+
+        let err = format!("anchor = 2,0\n{SHIP}")
+            .parse::<Sprite>()
+            .unwrap_err();
+        assert_eq!(err, "line 1: the anchor is outside of the 2x1 sprite");
+        let err = format!("\nanchor = 0,1\n{SHIP}")
+            .parse::<Sprite>()
+            .unwrap_err();
+        assert_eq!(err, "line 2: the anchor is outside of the 2x1 sprite");
+    }
+
+    #[test]
+    fn drawing_at_the_extremes() {
+        let sprite = SHIP.parse::<Sprite>().unwrap().with_anchor(pos2(1, 0));
+        let mut context = Context::new(rect(vec2(4, 2)));
+        for at in [
+            offset(i32::MIN, i32::MIN),
+            offset(i32::MAX, i32::MAX),
+            offset(i32::MIN, 0),
+            offset(0, i32::MAX),
+        ] {
+            sprite.draw(&mut context.canvas(), 0, at);
+        }
+        let surface = context.surface();
+        assert!(rect(vec2(4, 2))
+            .indices()
+            .all(|pos| surface[pos].char == ' '));
+
+        // only the left half is drawn at the right edge
+        let mut canvas = context.canvas();
+        canvas.blit(sprite.frame(0), sprite.frame(0).rect(), offset(3, 0));
+        assert_eq!(context.surface()[pos2(3, 0)].char, '<');
+    }
+
+    #[test]
+    fn frame_canvas_draws_on_the_frame() {
+        let mut sprite = SHIP.parse::<Sprite>().unwrap();
+        let index = sprite.add_frame();
+        sprite
+            .frame_canvas(index)
+            .put(pos2(1, 0), Cell::new('x').fg(0x0000FF));
+
+        assert_eq!(sprite.frame(index)[pos2(1, 0)].char, 'x');
+        assert!(sprite.is_opaque(index, pos2(1, 0)));
+        assert!(!sprite.is_opaque(index, pos2(0, 0)));
+        // this wraps around the number of frames
+        assert_eq!(sprite.frame_canvas(index + 2).area(), rect(vec2(2, 1)));
+    }
+}
@@ -6,8 +6,17 @@ pub use color::Color;
 mod rgb;
 pub use rgb::Rgb;
 
+mod rgba;
+pub use rgba::Rgba;
+
+mod blend;
+pub use blend::BlendMode;
+
 mod hsl;
 pub use hsl::Hsl;
 
 mod attribute;
 pub use attribute::Attribute;
+
+mod underline;
+pub use underline::Underline;
@@ -1,6 +1,6 @@
 /// Attributes to apply to text
 #[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Attribute(pub u8);
+pub struct Attribute(pub u16);
 
 #[cfg(feature = "serde")]
 impl serde::Serialize for Attribute {
@@ -32,6 +32,61 @@ impl Attribute {
     pub const BLINK: Self = Self(0b0001_0000);
     pub const REVERSE: Self = Self(0b0010_0000);
     pub const STRIKE_OUT: Self = Self(0b0100_0000);
+    pub const DOUBLE_UNDERLINE: Self = Self(0b1000_0000);
+    pub const RAPID_BLINK: Self = Self(0b0001_0000_0000);
+    pub const CONCEAL: Self = Self(0b0010_0000_0000);
+    pub const OVERLINE: Self = Self(0b0100_0000_0000);
+}
+
+impl Attribute {
+    /// The SGR codes that enable and disable each attribute
+    ///
+    /// Some attributes share the code that disables them, see [`Attribute::siblings`]
+    pub(crate) const CODES: [(Self, u8, u8); 11] = [
+        (Self::BOLD, 1, 22),
+        (Self::FAINT, 2, 22),
+        (Self::ITALIC, 3, 23),
+        (Self::UNDERLINE, 4, 24),
+        (Self::BLINK, 5, 25),
+        (Self::REVERSE, 7, 27),
+        (Self::STRIKE_OUT, 9, 29),
+        (Self::DOUBLE_UNDERLINE, 21, 24),
+        (Self::RAPID_BLINK, 6, 25),
+        (Self::CONCEAL, 8, 28),
+        (Self::OVERLINE, 53, 55),
+    ];
+
+    /// Is this attribute empty?
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Does this attribute have every flag of `other`?
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Does this attribute have any flag of `other`?
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// The attributes that are also disabled when disabling these attributes
+    ///
+    /// e.g. disabling [`Attribute::BOLD`] also disables [`Attribute::FAINT`]
+    pub fn siblings(self) -> Self {
+        let mut out = Self(0);
+        for (attr, _, off) in Self::CODES {
+            if self.intersects(attr) {
+                for (sibling, _, other) in Self::CODES {
+                    if other == off {
+                        out |= sibling;
+                    }
+                }
+            }
+        }
+        out
+    }
 }
 
 impl Attribute {
@@ -62,6 +117,22 @@ impl Attribute {
     pub const fn is_strike_out(&self) -> bool {
         self.0 & (1 << 6) != 0
     }
+
+    pub const fn is_double_underline(&self) -> bool {
+        self.0 & (1 << 7) != 0
+    }
+
+    pub const fn is_rapid_blink(&self) -> bool {
+        self.0 & (1 << 8) != 0
+    }
+
+    pub const fn is_conceal(&self) -> bool {
+        self.0 & (1 << 9) != 0
+    }
+
+    pub const fn is_overline(&self) -> bool {
+        self.0 & (1 << 10) != 0
+    }
 }
 
 impl std::ops::BitOr for Attribute {
@@ -99,13 +170,13 @@ impl std::ops::Not for Attribute {
 
 impl std::fmt::Binary for Attribute {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:08b}", self.0)
+        write!(f, "{:016b}", self.0)
     }
 }
 
 impl std::fmt::Debug for Attribute {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        const FIELDS: [&str; 7] = [
+        const FIELDS: [&str; 11] = [
             "Bold",
             "Faint",
             "Italic",
@@ -113,6 +184,10 @@ impl std::fmt::Debug for Attribute {
             "Blink",
             "Reverse",
             "StrikeOut",
+            "DoubleUnderline",
+            "RapidBlink",
+            "Conceal",
+            "Overline",
         ];
 
         let mut seen = false;
@@ -146,6 +221,10 @@ impl std::str::FromStr for Attribute {
                 s if s.eq_ignore_ascii_case("blink") => Self::BLINK,
                 s if s.eq_ignore_ascii_case("reverse") => Self::REVERSE,
                 s if s.eq_ignore_ascii_case("strikeout") => Self::STRIKE_OUT,
+                s if s.eq_ignore_ascii_case("doubleunderline") => Self::DOUBLE_UNDERLINE,
+                s if s.eq_ignore_ascii_case("rapidblink") => Self::RAPID_BLINK,
+                s if s.eq_ignore_ascii_case("conceal") => Self::CONCEAL,
+                s if s.eq_ignore_ascii_case("overline") => Self::OVERLINE,
                 attr => return Err(format!("unknown attribute: {attr}")),
             }
         }
@@ -0,0 +1,35 @@
+use super::Rgb;
+
+/// How an [`Rgba`](super::Rgba) color is combined with the color beneath it
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum BlendMode {
+    /// The color is drawn over the color beneath it
+    #[default]
+    Normal,
+    /// The colors are multiplied, this always darkens
+    Multiply,
+    /// The inverse of the colors are multiplied, this always lightens
+    Screen,
+    /// The colors are added together
+    Additive,
+}
+
+impl BlendMode {
+    /// Combine `top` with `base`, before the alpha is applied
+    pub fn apply(self, base: Rgb, top: Rgb) -> Rgb {
+        let op = |base: u8, top: u8| -> u8 {
+            let (base, top) = (base as u16, top as u16);
+            let out = match self {
+                Self::Normal => top,
+                Self::Multiply => base * top / 255,
+                Self::Screen => 255 - (255 - base) * (255 - top) / 255,
+                Self::Additive => (base + top).min(255),
+            };
+            out as u8
+        };
+
+        let (Rgb(r1, g1, b1), Rgb(r2, g2, b2)) = (base, top);
+        Rgb(op(r1, r2), op(g1, g2), op(b1, b2))
+    }
+}
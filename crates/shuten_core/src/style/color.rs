@@ -1,4 +1,4 @@
-use super::{Hsl, Rgb};
+use super::{BlendMode, Hsl, Rgb, Rgba};
 
 /// A color type thats used extensively in this crate
 ///
@@ -8,6 +8,8 @@ use super::{Hsl, Rgb};
 pub enum Color {
     /// Use an [`Rgb`] color
     Rgb(Rgb),
+    /// Blend an [`Rgba`] color over the existing color, with a [`BlendMode`]
+    Blend(Rgba, BlendMode),
     /// Reset the color
     Reset,
     /// Reuse the color
@@ -16,18 +18,59 @@ pub enum Color {
 }
 
 impl Color {
+    /// Blend an [`Rgba`] color over the existing color, with a [`BlendMode`]
+    pub fn blend(color: impl Into<Rgba>, mode: BlendMode) -> Self {
+        Self::Blend(color.into(), mode)
+    }
+
     pub fn lighten(self, ratio: f32) -> Self {
-        if let Self::Rgb(color) = self {
-            return Self::Rgb(color.lighten(ratio));
+        match self {
+            Self::Rgb(color) => Self::Rgb(color.lighten(ratio)),
+            Self::Blend(color, mode) => Self::Blend(color.lighten(ratio), mode),
+            _ => self,
         }
-        self
     }
 
     pub fn darken(self, ratio: f32) -> Self {
-        if let Self::Rgb(color) = self {
-            return Self::Rgb(color.darken(ratio));
+        match self {
+            Self::Rgb(color) => Self::Rgb(color.darken(ratio)),
+            Self::Blend(color, mode) => Self::Blend(color.darken(ratio), mode),
+            _ => self,
+        }
+    }
+
+    /// Composite this color over the `base` color
+    ///
+    /// - A [`Color::Blend`] over an [`Rgb`] produces the blended [`Rgb`]
+    /// - A [`Color::Blend`] over [`Color::Reuse`] is kept, so it can be composited later
+    /// - A [`Color::Blend`] over [`Color::Reset`] is composited over [`Rgb::DEFAULT_BG`], as the terminal color isn't known
+    /// - [`Color::Reuse`] produces the `base` color
+    ///
+    /// Any other color is returned as-is
+    ///
+    /// ```rust
+    /// use shuten_core::style::{BlendMode, Color, Rgb, Rgba};
+    /// let dim = Color::blend(Rgba::new(0, 0, 0, 128), BlendMode::Normal);
+    /// assert_eq!(dim.composite(Color::Reset), Color::Rgb(Rgb::DEFAULT_BG));
+    /// ```
+    pub fn composite(self, base: Self) -> Self {
+        self.composite_over(base, Rgb::DEFAULT_BG)
+    }
+
+    /// Composite this color over the `base` color, using `default` for [`Color::Reset`]
+    ///
+    /// See [`Color::composite`]
+    pub fn composite_over(self, base: Self, default: Rgb) -> Self {
+        match (self, base) {
+            (Self::Blend(color, mode), Self::Rgb(base)) => Self::Rgb(color.composite(base, mode)),
+            (Self::Blend(color, mode), Self::Blend(base, ..)) => {
+                Self::Rgb(color.composite(base.to_rgb(), mode))
+            }
+            (Self::Blend(..), Self::Reuse) => self,
+            (Self::Blend(color, mode), Self::Reset) => Self::Rgb(color.composite(default, mode)),
+            (Self::Reuse, base) => base,
+            (color, _) => color,
         }
-        self
     }
 }
 
@@ -55,6 +98,18 @@ impl From<&Rgb> for Color {
     }
 }
 
+impl From<Rgba> for Color {
+    fn from(value: Rgba) -> Self {
+        Self::Blend(value, BlendMode::Normal)
+    }
+}
+
+impl From<&Rgba> for Color {
+    fn from(value: &Rgba) -> Self {
+        Self::from(*value)
+    }
+}
+
 impl From<Option<Rgb>> for Color {
     fn from(value: Option<Rgb>) -> Self {
         value.map(Self::Rgb).unwrap_or(Self::Reset)
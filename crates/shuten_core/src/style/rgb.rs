@@ -42,6 +42,15 @@ impl std::fmt::UpperHex for Rgb {
 }
 
 impl Rgb {
+    /// The foreground color that is assumed for [`Color::Reset`](super::Color::Reset)
+    ///
+    /// The terminal's actual color isn't known
+    pub const DEFAULT_FG: Self = Self::from_u32(0xC0C0C0);
+    /// The background color that is assumed for [`Color::Reset`](super::Color::Reset)
+    ///
+    /// The terminal's actual color isn't known
+    pub const DEFAULT_BG: Self = Self::from_u32(0x000000);
+
     /// Create a new `r`,`g`,`b`
     pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Self(r, g, b)
@@ -131,6 +140,44 @@ impl Rgb {
         Self::new((r << 4) | r, (g << 4) | g, (b << 4) | b)
     }
 
+    /// Create an RGB from an index into the xterm 256-color palette
+    ///
+    /// - `0..16` are the standard and bright colors
+    /// - `16..232` are a 6x6x6 color cube
+    /// - `232..=255` are a grayscale ramp
+    ///
+    /// ```rust
+    /// use shuten_core::style::Rgb;
+    /// assert_eq!(Rgb::from_ansi256(9), Rgb::new(255, 0, 0));
+    /// assert_eq!(Rgb::from_ansi256(196), Rgb::new(255, 0, 0));
+    /// assert_eq!(Rgb::from_ansi256(232), Rgb::new(8, 8, 8));
+    /// ```
+    pub const fn from_ansi256(index: u8) -> Self {
+        const STANDARD: [u32; 16] = [
+            0x000000, 0x800000, 0x008000, 0x808000, 0x000080, 0x800080, 0x008080, 0xC0C0C0,
+            0x808080, 0xFF0000, 0x00FF00, 0xFFFF00, 0x0000FF, 0xFF00FF, 0x00FFFF, 0xFFFFFF,
+        ];
+        const fn level(n: u8) -> u8 {
+            if n == 0 {
+                0
+            } else {
+                55 + n * 40
+            }
+        }
+
+        match index {
+            0..=15 => Self::from_u32(STANDARD[index as usize]),
+            16..=231 => {
+                let index = index - 16;
+                Self::new(level(index / 36), level((index / 6) % 6), level(index % 6))
+            }
+            _ => {
+                let gray = 8 + (index - 232) * 10;
+                Self::new(gray, gray, gray)
+            }
+        }
+    }
+
     /// Mix this color with another color
     ///
     /// - `left` is the ratio of current color to mix in
@@ -189,7 +236,7 @@ impl Rgb {
     /// Format: `[r,g,b]`
     pub fn as_float(&self) -> [f32; 3] {
         let Self(r, g, b) = *self;
-        let scale = |d| (d as f32 / 256.0);
+        let scale = |d| d as f32 / 256.0;
         [scale(r), scale(g), scale(b)]
     }
 
@@ -275,10 +322,10 @@ impl FromStr for Rgb {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some(s) = s.strip_prefix('#') {
-            let digits = u16::from_str_radix(s, 16).map_err(|_| "invalid hex digits")?;
+            let digits = u32::from_str_radix(s, 16).map_err(|_| "invalid hex digits")?;
             return match s.len() {
-                3 => Ok(Self::from_u16(digits)),
-                6 => Ok(Self::from_u16(digits)),
+                3 => Ok(Self::from_u16(digits as u16)),
+                6 => Ok(Self::from_u32(digits)),
                 _ => Err("invalid hex-string, should be #rrggbb or #rgb"),
             };
         }
@@ -298,3 +345,17 @@ impl FromStr for Rgb {
         Err("rgb color must be in the form of rgb(r,g,b) or #rrggbb or #rgb")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex() {
+        assert_eq!("#123456".parse::<Rgb>(), Ok(Rgb::new(0x12, 0x34, 0x56)));
+        assert_eq!("#FFFFFF".parse::<Rgb>(), Ok(Rgb::new(255, 255, 255)));
+        assert_eq!("#123".parse::<Rgb>(), Ok(Rgb::new(0x11, 0x22, 0x33)));
+        assert!("#12345".parse::<Rgb>().is_err());
+        assert!("#12345g".parse::<Rgb>().is_err());
+    }
+}
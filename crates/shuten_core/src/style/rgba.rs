@@ -0,0 +1,95 @@
+use super::{BlendMode, Rgb};
+
+/// An [`Rgb`] color with an alpha channel
+///
+/// This is composited over the existing color when it is put onto a [`Canvas`](crate::Canvas)
+///
+/// ```rust
+/// use shuten_core::style::{BlendMode, Rgb, Rgba};
+/// let base = Rgb::from_u32(0x808080);
+/// let red = Rgba::from_u32(0xFF0000FF);
+/// assert_eq!(red.composite(base, BlendMode::Normal), Rgb::new(255, 0, 0));
+/// assert_eq!(red.composite(base, BlendMode::Multiply), Rgb::new(128, 0, 0));
+///
+/// let backdrop = Rgba::from_u32(0x00000080);
+/// assert!(backdrop.composite(base, BlendMode::Normal).red() < 128);
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, PartialOrd, Ord, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Rgba(
+    /// The red channel
+    pub u8,
+    /// The green channel
+    pub u8,
+    /// The blue channel
+    pub u8,
+    /// The alpha channel
+    pub u8,
+);
+
+impl std::fmt::Debug for Rgba {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self(r, g, b, a) = self;
+        write!(f, "rgba({r}, {g}, {b}, {a})")
+    }
+}
+
+impl Rgba {
+    /// Create a new `r`,`g`,`b`,`a`
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self(r, g, b, a)
+    }
+
+    /// Create an RGBA from a provided `rrggbbaa` `u32`
+    ///
+    /// ```rust
+    /// use shuten_core::style::Rgba;
+    /// const TRANSLUCENT_RED: Rgba = Rgba::from_u32(0xFF000080);
+    /// ```
+    pub const fn from_u32(rrggbbaa: u32) -> Self {
+        Self::new(
+            ((rrggbbaa >> 24) & 0xFF) as u8,
+            ((rrggbbaa >> 16) & 0xFF) as u8,
+            ((rrggbbaa >> 8) & 0xFF) as u8,
+            (rrggbbaa & 0xFF) as u8,
+        )
+    }
+
+    /// Get the alpha channel
+    pub const fn alpha(&self) -> u8 {
+        self.3
+    }
+
+    /// Get the [`Rgb`] part of this color, ignoring the alpha channel
+    pub const fn to_rgb(&self) -> Rgb {
+        Rgb(self.0, self.1, self.2)
+    }
+
+    /// Lighten this color by a ratio (in the range `0.0 ..= 1.0`), keeping its alpha
+    pub fn lighten(&self, ratio: f32) -> Self {
+        let Rgb(r, g, b) = self.to_rgb().lighten(ratio);
+        Self(r, g, b, self.3)
+    }
+
+    /// Darken this color by a ratio (in the range `0.0 ..= 1.0`), keeping its alpha
+    pub fn darken(&self, ratio: f32) -> Self {
+        let Rgb(r, g, b) = self.to_rgb().darken(ratio);
+        Self(r, g, b, self.3)
+    }
+
+    /// Composite this color over another color, using the [`BlendMode`]
+    pub fn composite(&self, base: Rgb, mode: BlendMode) -> Rgb {
+        let blended = mode.apply(base, self.to_rgb());
+        match self.alpha() {
+            0 => base,
+            u8::MAX => blended,
+            alpha => base.blend_flat(blended, alpha as f32 / 255.0),
+        }
+    }
+}
+
+impl From<Rgb> for Rgba {
+    fn from(Rgb(r, g, b): Rgb) -> Self {
+        Self(r, g, b, u8::MAX)
+    }
+}
@@ -0,0 +1,42 @@
+/// How an underline is drawn, when a cell has [`Attribute::UNDERLINE`](super::Attribute::UNDERLINE)
+///
+/// Terminals that don't support underline styles draw a single underline
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Underline {
+    /// A single straight line
+    #[default]
+    Single,
+    /// Two straight lines
+    Double,
+    /// A wavy line, commonly used for errors
+    Curly,
+    /// A dotted line
+    Dotted,
+    /// A dashed line
+    Dashed,
+}
+
+impl Underline {
+    /// The parameter used in an `SGR 4:n` sequence
+    pub(crate) const fn code(self) -> u8 {
+        match self {
+            Self::Single => 1,
+            Self::Double => 2,
+            Self::Curly => 3,
+            Self::Dotted => 4,
+            Self::Dashed => 5,
+        }
+    }
+
+    pub(crate) const fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            1 => Self::Single,
+            2 => Self::Double,
+            3 => Self::Curly,
+            4 => Self::Dotted,
+            5 => Self::Dashed,
+            _ => return None,
+        })
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{
-    geom::{pos2, Pos2, Vec2},
-    style::Color,
-};
+use std::ops::Range;
+
+use crate::geom::{pos2, Align2, Offset, Pos2, Rect, Vec2};
+
+mod ansi;
+mod export;
+mod raster;
 
 mod canvas;
 pub use canvas::Canvas;
@@ -9,6 +12,21 @@ pub use canvas::Canvas;
 mod cell;
 pub use cell::{Cell, CellAttr};
 
+mod dirty;
+use dirty::Span;
+
+mod grapheme;
+pub(crate) use grapheme::width as grapheme_width;
+pub use grapheme::Grapheme;
+use grapheme::Graphemes;
+
+mod link;
+use link::Links;
+pub use link::{Hyperlink, Link};
+
+mod region;
+pub use region::Connectivity;
+
 /// Surface is a grid that you can write cells to
 ///
 /// This is generally a lower-level type, normally you'd interact with the
@@ -16,6 +34,9 @@ pub use cell::{Cell, CellAttr};
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Surface {
     pub(crate) cells: Vec<Cell>,
+    graphemes: Graphemes,
+    links: Links,
+    dirty: Vec<Span>,
     size: Vec2,
 }
 
@@ -23,13 +44,132 @@ impl Surface {
     /// Create a new surface with the fixed size
     pub fn new(size: Vec2) -> Self {
         let cells = vec![Cell::EMPTY; size.x as usize * size.y as usize];
-        Self { cells, size }
+        Self {
+            cells,
+            graphemes: Graphemes::default(),
+            links: Links::default(),
+            dirty: vec![Span::full(size.x); size.y as usize],
+            size,
+        }
     }
 
     /// Resize the surface to a new size
-    #[cfg_attr(feature = "profiling", profiling::function)]
+    ///
+    /// This keeps the overlapping cells, anchored to the top-left. See [`Surface::resize_anchored`]
     pub fn resize(&mut self, size: Vec2) {
-        *self = Self::new(size)
+        self.resize_anchored(size, Align2::LEFT_TOP)
+    }
+
+    /// Resize the surface to a new size, keeping the overlapping cells aligned to the `anchor`
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::{pos2, vec2, Align2}, Cell, Surface};
+    /// let mut surface = Surface::new(vec2(4, 4));
+    /// surface[pos2(2, 2)] = Cell::new('a');
+    ///
+    /// // shrinking around the center drops a column and a row from each side
+    /// surface.resize_anchored(vec2(2, 2), Align2::CENTER_CENTER);
+    /// assert_eq!(surface[pos2(1, 1)], Cell::new('a'));
+    /// ```
+    ///
+    /// Any exposed cells are [empty](Cell::EMPTY) and the entire surface is marked as [dirty](Surface::mark_dirty)
+    pub fn resize_anchored(&mut self, size: Vec2, anchor: Align2) {
+        self.resize_with(size, anchor, Cell::EMPTY)
+    }
+
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub(crate) fn resize_with(&mut self, size: Vec2, anchor: Align2, fill: Cell) {
+        let old = std::mem::replace(self, Self::new(size));
+        self.cells.fill(fill);
+        // the kept cells still refer to the old graphemes and links
+        self.graphemes = old.graphemes;
+        self.links = old.links;
+
+        let (w, h) = (old.size.x.min(size.x), old.size.y.min(size.y));
+        if w == 0 || h == 0 {
+            self.compact();
+            return;
+        }
+
+        let src = pos2(
+            anchor.x.offset(size.x, old.size.x),
+            anchor.y.offset(size.y, old.size.y),
+        );
+        let dest = pos2(
+            anchor.x.offset(old.size.x, size.x),
+            anchor.y.offset(old.size.y, size.y),
+        );
+
+        for y in 0..h {
+            let from = pos_to_index(pos2(src.x, src.y + y), old.size.x);
+            let to = pos_to_index(pos2(dest.x, dest.y + y), size.x);
+            let row = &mut self.cells[to..to + w as usize];
+            row.copy_from_slice(&old.cells[from..from + w as usize]);
+
+            // wide cells split by the edges lose their other half
+            if let Some(first) = row.first_mut().filter(|cell| cell.is_continuation()) {
+                *first = first.char(' ');
+            }
+            if let Some(last) = row.last_mut().filter(|cell| cell.width() > 1) {
+                *last = last.char(' ');
+            }
+        }
+        self.compact();
+    }
+
+    /// Get the size of this surface
+    pub const fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Get the [`Rect`] of this surface, starting at the origin
+    pub fn rect(&self) -> Rect {
+        Rect::from_min_size(Pos2::ZERO, self.size)
+    }
+
+    /// Fill every cell with this [`Cell`], without compositing
+    ///
+    /// Filling with [`Cell::TRANSPARENT`] makes the entire surface transparent when it is [blitted](Canvas::blit)
+    ///
+    /// Any [`Grapheme`] or [`Link`] that isn't used by the `cell` is forgotten
+    pub fn fill(&mut self, cell: Cell) {
+        self.cells.fill(cell);
+        self.dirty.fill(Span::full(self.size.x));
+        self.compact();
+    }
+
+    /// Mark the cells in this [`Rect`] as dirty, so they are compared by the next [`Surface::diff`]
+    ///
+    /// Writing to a cell marks it as dirty, so this is only needed when the other surface has changed
+    pub fn mark_dirty(&mut self, rect: Rect) {
+        let rect = rect.intersect(self.rect());
+        let columns = rect.left()..rect.left() + rect.width();
+        for y in rect.top()..rect.top() + rect.height() {
+            self.dirty[y as usize].mark(columns.clone())
+        }
+    }
+
+    /// Mark every cell as clean, so they are skipped by the next [`Surface::diff`]
+    pub fn mark_clean(&mut self) {
+        self.dirty.fill(Span::CLEAN)
+    }
+
+    /// Mark the cells that are dirty on `other` as dirty on this surface
+    pub(crate) fn merge_dirty(&mut self, other: &Self) {
+        for (span, other) in self.dirty.iter_mut().zip(&other.dirty) {
+            span.merge(*other)
+        }
+    }
+
+    fn mark(&mut self, pos: Pos2) {
+        self.dirty[pos.y as usize].mark(pos.x..pos.x + 1)
+    }
+
+    /// Create a [`Canvas`] that covers this entire surface
+    ///
+    /// This allows you to draw onto an offscreen surface, which can later be [blitted](Canvas::blit) onto another [`Canvas`]
+    pub fn canvas(&mut self) -> Canvas<'_> {
+        Canvas::new(self.rect(), self)
     }
 
     /// Does this surface contain this point?
@@ -37,25 +177,202 @@ impl Surface {
         pos.x < self.size.x && pos.y < self.size.y
     }
 
+    /// Scroll these rows by an amount
+    ///
+    /// - Positive amounts scroll the rows up, blank rows are exposed at the bottom
+    /// - Negative amounts scroll the rows down, blank rows are exposed at the top
+    ///
+    /// The exposed rows are filled with [`Cell::RESET`]
+    pub fn scroll(&mut self, rows: Range<u16>, amount: i32) {
+        let w = self.size.x as usize;
+        let (start, end) = (rows.start as usize, rows.end.min(self.size.y) as usize);
+        if start >= end {
+            return;
+        }
+
+        let n = (amount.unsigned_abs() as usize).min(end - start);
+        let (exposed, src, dest) = match amount.signum() {
+            1 => (end - n..end, start + n..end, start),
+            -1 => (start..start + n, start..end - n, start + n),
+            _ => return,
+        };
+
+        self.cells.copy_within(src.start * w..src.end * w, dest * w);
+        self.cells[exposed.start * w..exposed.end * w].fill(Cell::RESET);
+
+        // the dirty rows move along with their cells
+        self.dirty.copy_within(src, dest);
+        self.dirty[exposed].fill(Span::full(self.size.x));
+    }
+
+    /// Intern a grapheme cluster, so it can be used by a [`Cell`] on this surface
+    ///
+    /// See [`Canvas::put_grapheme`](crate::Canvas::put_grapheme) for an easier way of doing this
+    pub fn intern(&mut self, grapheme: &str) -> Grapheme {
+        self.graphemes.intern(grapheme)
+    }
+
+    /// Get the grapheme cluster for this [`Cell`], if it has one
+    pub fn grapheme(&self, cell: &Cell) -> Option<&str> {
+        cell.grapheme.and_then(|id| self.graphemes.get(id))
+    }
+
+    /// Intern a hyperlink, so it can be used by a [`Cell`] on this surface
+    ///
+    /// Interning the same `uri` and `id` returns the same [`Link`]
+    ///
+    /// Bytes that aren't printable ASCII are percent-encoded, as are a `;` or `:` in the `id`
+    ///
+    /// See [`Canvas::link`](crate::Canvas::link) for an easier way of doing this
+    pub fn intern_link(&mut self, uri: &str, id: Option<&str>) -> Link {
+        self.links.intern(uri, id)
+    }
+
+    /// Get the hyperlink for this [`Cell`], if it has one
+    pub fn link(&self, cell: &Cell) -> Option<Hyperlink<'_>> {
+        cell.link.and_then(|link| self.links.get(link))
+    }
+
+    /// Rebuild the grapheme and link arenas from the cells, forgetting the ones that aren't used anymore
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub(crate) fn compact(&mut self) {
+        let graphemes = std::mem::take(&mut self.graphemes);
+        let links = std::mem::take(&mut self.links);
+        for cell in &mut self.cells {
+            cell.grapheme = cell
+                .grapheme
+                .and_then(|id| graphemes.get(id))
+                .map(|grapheme| self.graphemes.intern(grapheme));
+            cell.link = cell
+                .link
+                .and_then(|link| links.get(link))
+                .map(|Hyperlink { uri, id }| self.links.intern(uri, id));
+        }
+    }
+
+    /// [Compact](Surface::compact) the arenas once they are larger than the number of cells, as some of them must be unused
+    pub(crate) fn trim(&mut self) {
+        let len = self.cells.len();
+        if self.graphemes.len() > len || self.links.len() > len {
+            self.compact()
+        }
+    }
+
     /// Generate a diff of two surfaces, yielding the [locations](pos2) and [`Cell`]s that are different
     ///
+    /// Only the [dirty](Surface::mark_dirty) cells of the `other` surface are compared
+    ///
     /// This mutates the original cell to cache future changes
+    ///
+    /// Wide cells are yielded as a single change, their [continuation](Cell::CONTINUATION) is never yielded
+    ///
+    /// Any [`Grapheme`] or [`Link`] in the yielded [`Cell`]s belong to the `other` surface
     #[cfg_attr(feature = "profiling", profiling::function)]
-    pub fn diff<'a>(&'a mut self, other: &'a Self) -> impl Iterator<Item = (Pos2, Cell)> + '_ {
-        // TODO skip to the first change
-        // TODO stop at the last change
-        self.cells
-            .iter_mut()
-            .zip(other.cells.iter().copied())
+    pub fn diff<'a>(&'a mut self, other: &'a Self) -> impl Iterator<Item = (Pos2, Cell)> + 'a {
+        let w = self.size.x as usize;
+        let mut rows = other
+            .dirty
+            .iter()
             .enumerate()
-            .filter_map(|(i, (left, right)): (usize, (&mut Cell, Cell))| {
-                if *left == right || (right.fg == Color::Reuse && right.bg == Color::Reuse) {
-                    return None;
+            .filter(|(_, span)| !span.is_clean());
+
+        let (mut index, mut end) = (0, 0);
+        std::iter::from_fn(move || loop {
+            while index < end {
+                let i = index;
+                index += 1;
+
+                let right = other.cells[i];
+                if right.is_transparent() {
+                    continue;
                 }
 
-                *left = right;
-                Some((index_to_pos(i, self.size.x), right))
-            })
+                // the leading half and its continuation are synchronized together
+                let paired = right.width() > 1
+                    && (i + 1) % w != 0
+                    && other.cells.get(i + 1).is_some_and(Cell::is_continuation);
+
+                if paired {
+                    index += 1;
+                    if self.same_cell(i, other) && self.same_cell(i + 1, other) {
+                        continue;
+                    }
+                    self.cells[i + 1] = other.cells[i + 1];
+                } else if self.same_cell(i, other) {
+                    continue;
+                }
+
+                self.cells[i] = self.adopt(other, right);
+
+                // an orphaned continuation has nothing to continue, so its drawn as a blank
+                let change = if right.is_continuation() {
+                    right.char(' ')
+                } else {
+                    right
+                };
+                return Some((index_to_pos(i, self.size.x), change));
+            }
+
+            // include the neighbors, so both halves of a wide cell are visited
+            let (y, span) = rows.next()?;
+            let columns = span.columns();
+            let start = y * w + columns.start.saturating_sub(1) as usize;
+            (index, end) = (start, y * w + (columns.end as usize + 1).min(w));
+        })
+    }
+
+    /// Is the cell at `index` the same for both surfaces?
+    ///
+    /// Graphemes and links are compared by their contents, as each surface interns its own
+    fn same_cell(&self, index: usize, other: &Self) -> bool {
+        let (left, right) = (self.cells[index], other.cells[index]);
+        let interned = |cell: &Cell| cell.grapheme.is_some() || cell.link.is_some();
+        if !interned(&left) && !interned(&right) {
+            return left == right;
+        }
+
+        let strip = |cell: Cell| Cell {
+            grapheme: None,
+            link: None,
+            ..cell
+        };
+        strip(left) == strip(right)
+            && self.grapheme(&left) == other.grapheme(&right)
+            && self.link(&left) == other.link(&right)
+    }
+
+    /// Intern the grapheme and link of a [`Cell`] from the `other` surface, so it can be used on this surface
+    pub(crate) fn adopt(&mut self, other: &Self, cell: Cell) -> Cell {
+        Cell {
+            grapheme: other.grapheme(&cell).map(|grapheme| self.intern(grapheme)),
+            link: other
+                .link(&cell)
+                .map(|Hyperlink { uri, id }| self.intern_link(uri, id)),
+            ..cell
+        }
+    }
+
+    /// Composite the non-transparent cells of `layer` over this surface
+    pub(crate) fn composite(&mut self, layer: &Self) {
+        self.canvas().blit(layer, layer.rect(), Offset::ZERO)
+    }
+
+    /// Clear the other half of a wide cell at this point, if any
+    ///
+    /// The orphaned half is replaced with a blank, keeping its colors
+    pub(crate) fn clear_wide(&mut self, pos: Pos2) {
+        let cell = self[pos];
+        if cell.is_continuation() && pos.x > 0 {
+            let lead = &mut self[pos2(pos.x - 1, pos.y)];
+            if lead.width() > 1 {
+                *lead = lead.char(' ');
+            }
+        } else if cell.width() > 1 && pos.x + 1 < self.size.x {
+            let next = &mut self[pos2(pos.x + 1, pos.y)];
+            if next.is_continuation() {
+                next.char = ' ';
+            }
+        }
     }
 }
 
@@ -88,15 +405,192 @@ impl std::ops::IndexMut<Pos2> for Surface {
             w = self.size.x,
             h = self.size.y
         );
+        self.mark(index);
         &mut self.cells[pos_to_index(index, self.size.x)]
     }
 }
 
+// a surface can have more than `u16::MAX` cells, so this is done in `usize`
 const fn pos_to_index(pos: Pos2, w: u16) -> usize {
-    (pos.y * w + pos.x) as usize
+    pos.y as usize * w as usize + pos.x as usize
 }
 
 const fn index_to_pos(index: usize, w: u16) -> Pos2 {
-    let index = index as u16;
-    pos2(index % w, index / w)
+    let w = w as usize;
+    pos2((index % w) as u16, (index / w) as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::vec2;
+
+    fn dirty_rows(surface: &Surface) -> Vec<u16> {
+        (0..)
+            .zip(&surface.dirty)
+            .filter_map(|(y, span)| (!span.is_clean()).then_some(y))
+            .collect()
+    }
+
+    #[test]
+    fn new_surface_is_dirty() {
+        let surface = Surface::new(vec2(3, 2));
+        assert_eq!(dirty_rows(&surface), [0, 1]);
+    }
+
+    #[test]
+    fn writing_marks_the_cell() {
+        let mut surface = Surface::new(vec2(10, 3));
+        surface.mark_clean();
+        assert!(dirty_rows(&surface).is_empty());
+
+        surface[pos2(4, 1)] = Cell::new('a');
+        surface[pos2(7, 1)] = Cell::new('b');
+        assert_eq!(dirty_rows(&surface), [1]);
+        assert_eq!(surface.dirty[1].columns(), 4..8);
+    }
+
+    #[test]
+    fn diff_only_visits_dirty_rows() {
+        let mut front = Surface::new(vec2(4, 3));
+        let mut back = Surface::new(vec2(4, 3));
+        back.mark_clean();
+
+        // this change isn't marked, so it is skipped
+        back.cells[pos_to_index(pos2(1, 2), 4)] = Cell::new('x');
+        back[pos2(2, 0)] = Cell::new('y');
+
+        let changes: Vec<_> = front
+            .diff(&back)
+            .map(|(pos, cell)| (pos, cell.char))
+            .collect();
+        assert_eq!(changes, [(pos2(2, 0), 'y')]);
+        assert_eq!(front[pos2(1, 2)].char, ' ');
+    }
+
+    #[test]
+    fn diff_skips_unchanged_dirty_cells() {
+        let mut front = Surface::new(vec2(4, 1));
+        let mut back = Surface::new(vec2(4, 1));
+        back[pos2(0, 0)] = Cell::new('a');
+        assert_eq!(front.diff(&back).count(), 1);
+
+        // the cell is marked, but it's the same
+        back.mark_clean();
+        back[pos2(0, 0)] = Cell::new('a');
+        assert_eq!(front.diff(&back).count(), 0);
+    }
+
+    #[test]
+    fn scroll_up_moves_dirty_rows() {
+        let mut surface = Surface::new(vec2(3, 4));
+        surface.mark_clean();
+        surface[pos2(0, 2)] = Cell::new('a');
+
+        surface.scroll(0..4, 1);
+        assert_eq!(surface[pos2(0, 1)].char, 'a');
+        // the written row moved up, and the bottom row was exposed
+        assert_eq!(dirty_rows(&surface), [1, 3]);
+        assert_eq!(surface.dirty[3], Span::full(3));
+    }
+
+    #[test]
+    fn scroll_down_moves_dirty_rows() {
+        let mut surface = Surface::new(vec2(3, 4));
+        surface.mark_clean();
+        surface[pos2(0, 1)] = Cell::new('a');
+
+        surface.scroll(1..4, -2);
+        assert_eq!(surface[pos2(0, 3)].char, 'a');
+        assert_eq!(dirty_rows(&surface), [1, 2, 3]);
+    }
+
+    #[test]
+    fn scrolled_rows_match_after_the_front_is_scrolled() {
+        let mut front = Surface::new(vec2(3, 3));
+        let mut back = Surface::new(vec2(3, 3));
+        for (y, char) in (0..).zip("abc".chars()) {
+            back[pos2(0, y)] = Cell::new(char);
+        }
+        assert_eq!(front.diff(&back).count(), 3);
+        back.mark_clean();
+
+        back.scroll(0..3, 1);
+        front.scroll(0..3, 1);
+        // only the exposed row is compared, and it matches
+        assert_eq!(front.diff(&back).count(), 0);
+        assert_eq!(front[pos2(0, 0)].char, 'b');
+    }
+
+    #[test]
+    fn fill_forgets_unused_graphemes_and_links() {
+        let mut surface = Surface::new(vec2(3, 1));
+        let mut canvas = surface.canvas();
+        let link = canvas.link("https://example.com", None);
+        canvas.put_grapheme(pos2(0, 0), "e\u{301}", Cell::EMPTY.link(link));
+        canvas.put_grapheme(pos2(1, 0), "a\u{301}", Cell::EMPTY);
+
+        let cell = surface[pos2(1, 0)];
+        surface.fill(cell);
+        assert_eq!(surface.graphemes.len(), 1);
+        assert_eq!(surface.links.len(), 0);
+        assert_eq!(surface.grapheme(&surface[pos2(2, 0)]), Some("a\u{301}"));
+    }
+
+    #[test]
+    fn resize_forgets_dropped_graphemes() {
+        let mut surface = Surface::new(vec2(3, 1));
+        let mut canvas = surface.canvas();
+        canvas.put_grapheme(pos2(0, 0), "e\u{301}", Cell::EMPTY);
+        canvas.put_grapheme(pos2(2, 0), "a\u{301}", Cell::EMPTY);
+
+        surface.resize(vec2(2, 1));
+        assert_eq!(surface.graphemes.len(), 1);
+        assert_eq!(surface.grapheme(&surface[pos2(0, 0)]), Some("e\u{301}"));
+    }
+
+    #[test]
+    fn arenas_stay_bounded() {
+        let mut front = Surface::new(vec2(2, 2));
+        let mut back = Surface::new(vec2(2, 2));
+        for i in 0..100 {
+            let mut canvas = back.canvas();
+            let grapheme = format!("{}\u{301}", char::from_u32(0x4E00 + i).unwrap());
+            let link = canvas.link(&format!("https://example.com/{i}"), None);
+            canvas.put_grapheme(pos2(0, 0), &grapheme, Cell::EMPTY.link(link));
+
+            assert_eq!(front.diff(&back).count(), 1);
+            front.trim();
+            back.trim();
+            assert!(front.graphemes.len() <= 4 && back.graphemes.len() <= 4);
+            assert!(front.links.len() <= 4 && back.links.len() <= 4);
+
+            // the interned cell still refers to the same grapheme and link
+            assert_eq!(front.grapheme(&front[pos2(0, 0)]), Some(&*grapheme));
+            let uri = format!("https://example.com/{i}");
+            assert_eq!(front.link(&front[pos2(0, 0)]).unwrap().uri, uri);
+        }
+    }
+
+    #[test]
+    fn more_cells_than_u16() {
+        let size = vec2(400, 200);
+        let mut back = Surface::new(size);
+        let last = pos2(399, 199);
+        back.canvas().put(last, Cell::new('z'));
+        back.canvas().put(pos2(1, 180), Cell::new('y'));
+        assert_eq!(back[last].char, 'z');
+
+        let mut front = Surface::new(size);
+        let changes: Vec<_> = front
+            .diff(&back)
+            .filter(|(_, cell)| cell.char != ' ')
+            .map(|(pos, cell)| (pos, cell.char))
+            .collect();
+        assert_eq!(changes, [(pos2(1, 180), 'y'), (last, 'z')]);
+
+        back.resize_anchored(vec2(300, 250), Align2::RIGHT_BOTTOM);
+        assert_eq!(back[pos2(299, 249)].char, 'z');
+        assert!(back.to_svg().contains("z</text>"));
+    }
 }
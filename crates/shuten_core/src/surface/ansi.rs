@@ -0,0 +1,481 @@
+use std::{fmt::Write as _, num::IntErrorKind};
+
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use super::{grapheme_width, Cell, CellAttr, Surface};
+use crate::{
+    geom::{pos2, vec2, Vec2},
+    style::{Attribute, Color, Rgb, Underline},
+};
+
+impl Surface {
+    /// The largest [`Surface`] that [`Surface::from_ansi`] creates
+    ///
+    /// Anything beyond this is truncated, so untrusted input can't allocate an unbounded surface
+    pub const MAX_ANSI_SIZE: Vec2 = vec2(512, 2048);
+
+    /// Create a [`Surface`] from text with embedded [SGR](https://en.wikipedia.org/wiki/ANSI_escape_code#SGR) escape sequences, e.g. an `.ans` file
+    ///
+    /// The surface is sized to fit the longest line, up to [`Surface::MAX_ANSI_SIZE`]. Columns and rows beyond that are dropped
+    ///
+    /// - Truecolor (`38;2;r;g;b`), 256-color (`38;5;n`) and the 16 standard colors are supported
+    /// - Underline styles (`4:3`) and underline colors (`58;2;r;g;b`) are supported
+    /// - Cursor forward (`CSI n C`) skips columns, other escape sequences are ignored
+    /// - Anything after a `SUB` (`^Z`), such as a SAUCE record, is ignored
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::{pos2, vec2}, style::{Attribute, Rgb}, Cell, Surface};
+    /// let surface = Surface::from_ansi("\x1b[1;31mhi\x1b[0m\nthere");
+    /// assert_eq!(surface.size(), vec2(5, 2));
+    ///
+    /// let cell = surface[pos2(1, 0)];
+    /// assert_eq!(cell.char, 'i');
+    /// assert_eq!(cell.fg, Rgb::from_ansi256(1).into());
+    /// assert_eq!(cell.attr, Cell::EMPTY.attr(Attribute::BOLD).attr);
+    /// ```
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub fn from_ansi(input: &str) -> Self {
+        let input = input.split('\x1a').next().unwrap_or_default();
+
+        let mut rows = vec![vec![]];
+        let mut style = Style::default();
+        let (mut x, mut width) = (0_u16, 0_u16);
+
+        let max = Self::MAX_ANSI_SIZE;
+        let mut rest = input;
+        'parse: while !rest.is_empty() {
+            if let Some(tail) = rest.strip_prefix("\x1b[") {
+                let end = tail
+                    .find(|c| matches!(c, '\x40'..='\x7e'))
+                    .unwrap_or(tail.len());
+                let params = &tail[..end];
+                match tail[end..].chars().next() {
+                    Some('m') => style.apply(params),
+                    Some('C') => {
+                        let columns = match params.parse::<u16>() {
+                            Ok(columns) => columns.max(1),
+                            Err(err) if *err.kind() == IntErrorKind::PosOverflow => max.x,
+                            Err(_) => 1,
+                        };
+                        x = x.saturating_add(columns).min(max.x)
+                    }
+                    _ => {}
+                }
+                rest = tail.get(end + 1..).unwrap_or_default();
+                width = width.max(x);
+                continue;
+            }
+
+            // operating system commands (e.g. titles) end with a BEL or an ST
+            if let Some(tail) = rest.strip_prefix("\x1b]") {
+                let end = tail.find(['\x07', '\x1b']).unwrap_or(tail.len());
+                rest = &tail[end..];
+                rest = rest
+                    .strip_prefix('\x07')
+                    .or_else(|| rest.strip_prefix("\x1b\\"))
+                    .unwrap_or(rest);
+                continue;
+            }
+
+            if let Some(tail) = rest.strip_prefix('\x1b') {
+                rest = tail;
+                continue;
+            }
+
+            let end = rest.find('\x1b').unwrap_or(rest.len());
+            let (text, tail) = rest.split_at(end);
+            rest = tail;
+
+            for grapheme in text.graphemes(true) {
+                match grapheme {
+                    "\n" | "\r\n" if rows.len() >= max.y as usize => break 'parse,
+                    "\n" | "\r\n" => {
+                        rows.push(vec![]);
+                        x = 0;
+                    }
+                    "\r" => x = 0,
+                    "\t" => x = (x / 8 + 1).saturating_mul(8).min(max.x),
+                    grapheme if grapheme.chars().all(char::is_control) => {}
+                    _ if x >= max.x => {}
+                    grapheme => {
+                        let row = rows.last_mut().expect("there is always a row");
+                        row.push((x, grapheme, style.cell()));
+                        x = x.saturating_add(grapheme_width(grapheme)).min(max.x);
+                    }
+                }
+                width = width.max(x);
+            }
+        }
+
+        // a trailing newline doesn't start a new row
+        if rows.len() > 1 && rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let mut surface = Self::new(vec2(width, rows.len() as u16));
+        surface.fill(Cell::RESET);
+
+        let mut canvas = surface.canvas();
+        for (y, row) in (0..).zip(rows) {
+            for (x, grapheme, cell) in row {
+                canvas.put_grapheme(pos2(x, y), grapheme, cell)
+            }
+        }
+        surface
+    }
+
+    /// Write this [`Surface`] as text with embedded [SGR](https://en.wikipedia.org/wiki/ANSI_escape_code#SGR) escape sequences
+    ///
+    /// The output is self-contained: every line ends with a reset, so it can be written directly to a terminal
+    ///
+    /// [`Color::Reuse`] keeps the previous color on the line, just as it does when rendering
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::pos2, Surface};
+    /// let surface = Surface::from_ansi("\x1b[38;2;255;0;0mred\x1b[0m");
+    /// let ansi = surface.to_ansi();
+    /// assert!(ansi.starts_with("\x1b[0;38;2;255;0;0mred"));
+    /// assert_eq!(Surface::from_ansi(&ansi), surface);
+    /// ```
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+
+        for y in 0..self.size.y {
+            let mut current = Style::default();
+            let mut written = false;
+
+            for x in 0..self.size.x {
+                let cell = self[pos2(x, y)];
+                if cell.is_continuation() {
+                    continue;
+                }
+
+                let next = current.update(&cell);
+                if !written || next != current {
+                    next.write_sgr(&mut out);
+                    (current, written) = (next, true);
+                }
+
+                match self.grapheme(&cell) {
+                    Some(grapheme) => out.push_str(grapheme),
+                    None => out.push(cell.char),
+                }
+            }
+
+            out.push_str("\x1b[0m");
+            if y + 1 < self.size.y {
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// The current graphics state
+///
+/// `None` colors are the terminal's default colors
+#[derive(Copy, Clone, Default, PartialEq)]
+pub(super) struct Style {
+    pub(super) fg: Option<Rgb>,
+    pub(super) bg: Option<Rgb>,
+    pub(super) attr: Attribute,
+    pub(super) underline: Underline,
+    pub(super) underline_color: Option<Rgb>,
+}
+
+impl Style {
+    fn cell(&self) -> Cell {
+        let color = |color: Option<Rgb>| color.map_or(Color::Reset, Color::Rgb);
+        let mut cell = Cell::new(' ')
+            .fg(color(self.fg))
+            .bg(color(self.bg))
+            .attr((self.attr != Attribute::default()).then_some(self.attr))
+            .underline_color(color(self.underline_color));
+        if self.attr.is_underline() {
+            cell.underline = self.underline;
+        }
+        cell
+    }
+
+    /// Apply the parameters of an SGR sequence
+    ///
+    /// Parameters are separated by `;`, and can have sub-parameters separated by `:`
+    fn apply(&mut self, params: &str) {
+        // empty parameters (and an empty sequence) are a reset
+        let number = |param: &str| param.parse::<u16>().unwrap_or(0);
+        let mut params = params.split(';');
+
+        while let Some(param) = params.next() {
+            let mut sub = param.split(':').map(number);
+            let code = sub.next().unwrap_or(0);
+
+            // extended colors are either `38;2;r;g;b` or `38:2:r:g:b`
+            let mut color = |current: Option<Rgb>| {
+                let color = match param.contains(':') {
+                    true => colon_extended(sub.by_ref()),
+                    false => extended(&mut params.by_ref().map(number)),
+                };
+                color.or(current)
+            };
+
+            match code {
+                0 => *self = Self::default(),
+                4 => match sub.next() {
+                    Some(0) => self.attr &= !Attribute::UNDERLINE,
+                    style => {
+                        self.attr |= Attribute::UNDERLINE;
+                        self.underline = style.and_then(Underline::from_code).unwrap_or_default();
+                    }
+                },
+                30..=37 => self.fg = Some(Rgb::from_ansi256((code - 30) as u8)),
+                38 => self.fg = color(self.fg),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(Rgb::from_ansi256((code - 40) as u8)),
+                48 => self.bg = color(self.bg),
+                49 => self.bg = None,
+                58 => self.underline_color = color(self.underline_color),
+                59 => self.underline_color = None,
+                90..=97 => self.fg = Some(Rgb::from_ansi256((code - 90 + 8) as u8)),
+                100..=107 => self.bg = Some(Rgb::from_ansi256((code - 100 + 8) as u8)),
+                code => {
+                    for (flag, on, off) in Attribute::CODES {
+                        if code == on as u16 {
+                            self.attr |= flag;
+                        } else if code == off as u16 {
+                            self.attr &= !flag;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The style after drawing this cell
+    pub(super) fn update(&self, cell: &Cell) -> Self {
+        let color = |color: Color, current: Option<Rgb>| match color {
+            Color::Rgb(rgb) => Some(rgb),
+            Color::Blend(rgba, ..) => Some(rgba.to_rgb()),
+            Color::Reset => None,
+            Color::Reuse => current,
+        };
+        Self {
+            fg: color(cell.fg, self.fg),
+            bg: color(cell.bg, self.bg),
+            attr: match cell.attr {
+                CellAttr::Merge { add, remove } => (self.attr & !remove) | add,
+                attr => attr.flags(),
+            },
+            underline: cell.underline,
+            underline_color: color(cell.underline_color, self.underline_color),
+        }
+    }
+
+    /// Write this style as a single SGR sequence, starting from a reset
+    fn write_sgr(&self, out: &mut String) {
+        out.push_str("\x1b[0");
+
+        for (flag, code, _) in Attribute::CODES {
+            if !self.attr.intersects(flag) {
+                continue;
+            }
+            _ = write!(out, ";{code}");
+            if flag == Attribute::UNDERLINE && self.underline != Underline::Single {
+                _ = write!(out, ":{}", self.underline.code());
+            }
+        }
+
+        if let Some(Rgb(r, g, b)) = self.fg {
+            _ = write!(out, ";38;2;{r};{g};{b}");
+        }
+        if let Some(Rgb(r, g, b)) = self.bg {
+            _ = write!(out, ";48;2;{r};{g};{b}");
+        }
+        if let Some(Rgb(r, g, b)) = self.underline_color {
+            _ = write!(out, ";58;2;{r};{g};{b}");
+        }
+        out.push('m');
+    }
+}
+
+/// Parse the color of an extended color sequence, `5;n` or `2;r;g;b`
+fn extended(params: &mut impl Iterator<Item = u16>) -> Option<Rgb> {
+    let mut channel = || params.next().map(|n| n.min(255) as u8);
+    match channel()? {
+        5 => channel().map(Rgb::from_ansi256),
+        2 => Some(Rgb::new(channel()?, channel()?, channel()?)),
+        _ => None,
+    }
+}
+
+/// Parse the color of an extended color sequence with sub-parameters, `5:n`, `2:r:g:b` or `2::r:g:b`
+fn colon_extended(params: impl Iterator<Item = u16>) -> Option<Rgb> {
+    let params = params.collect::<Vec<_>>();
+    match params.as_slice() {
+        // the color space id is ignored
+        &[2, _, r, g, b, ..] => extended(&mut [2, r, g, b].into_iter()),
+        params => extended(&mut params.iter().copied()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(params: &str) -> Style {
+        let mut style = Style::default();
+        style.apply(params);
+        style
+    }
+
+    fn round_trip(surface: &Surface) {
+        let ansi = surface.to_ansi();
+        assert_eq!(Surface::from_ansi(&ansi), *surface, "{ansi:?}");
+    }
+
+    #[test]
+    fn sgr_reset() {
+        assert!(style("1;31;0") == Style::default());
+        assert!(style("1;31;") == Style::default());
+
+        let surface = Surface::from_ansi("\x1b[1;31ma\x1b[mb\x1b[0;4mc");
+        assert_eq!(surface[pos2(0, 0)].fg, Rgb::from_ansi256(1).into());
+        assert_eq!(surface[pos2(1, 0)].fg, Color::Reset);
+        assert_eq!(surface[pos2(1, 0)].attr, CellAttr::Reset);
+        assert_eq!(
+            surface[pos2(2, 0)].attr,
+            CellAttr::Attr(Attribute::UNDERLINE)
+        );
+        round_trip(&surface);
+    }
+
+    #[test]
+    fn standard_and_bright_colors() {
+        let style = style("32;107");
+        assert!(style.fg == Some(Rgb::from_ansi256(2)));
+        assert!(style.bg == Some(Rgb::from_ansi256(15)));
+
+        round_trip(&Surface::from_ansi("\x1b[30;47ma\x1b[97;100mb\x1b[39;49mc"));
+    }
+
+    #[test]
+    fn ansi256_colors() {
+        let style = style("38;5;196;48;5;232");
+        assert!(style.fg == Some(Rgb::new(255, 0, 0)));
+        assert!(style.bg == Some(Rgb::new(8, 8, 8)));
+
+        round_trip(&Surface::from_ansi("\x1b[38;5;21mblue\x1b[48;5;250mgray"));
+    }
+
+    #[test]
+    fn truecolor() {
+        let style = style("38;2;1;2;3;48;2;4;5;6;58;2;7;8;9");
+        assert!(style.fg == Some(Rgb::new(1, 2, 3)));
+        assert!(style.bg == Some(Rgb::new(4, 5, 6)));
+        assert!(style.underline_color == Some(Rgb::new(7, 8, 9)));
+
+        // channels are clamped
+        assert!(self::style("38;2;300;0;0").fg == Some(Rgb::new(255, 0, 0)));
+        // a truncated color is ignored
+        assert!(self::style("38;2;1").fg.is_none());
+
+        round_trip(&Surface::from_ansi(
+            "\x1b[4;38;2;10;20;30;58;2;1;1;1mx\x1b[48;2;0;0;0my",
+        ));
+    }
+
+    #[test]
+    fn colon_sub_parameters() {
+        assert!(style("38:2:1:2:3").fg == Some(Rgb::new(1, 2, 3)));
+        // with the color space id
+        assert!(style("38:2::1:2:3").fg == Some(Rgb::new(1, 2, 3)));
+        assert!(style("48:5:9").bg == Some(Rgb::from_ansi256(9)));
+        // sub-parameters don't consume the next parameter
+        let style = style("38:5:1;1");
+        assert!(style.fg == Some(Rgb::from_ansi256(1)));
+        assert!(style.attr.is_bold());
+
+        let curly = self::style("4:3");
+        assert!(curly.attr.is_underline());
+        assert!(curly.underline == Underline::Curly);
+        assert!(!self::style("4:3;4:0").attr.is_underline());
+
+        let surface = Surface::from_ansi("\x1b[4:3;58:2::255:0:0mwavy\x1b[4:5mdash");
+        assert_eq!(surface[pos2(0, 0)].underline, Underline::Curly);
+        assert_eq!(
+            surface[pos2(0, 0)].underline_color,
+            Rgb::new(255, 0, 0).into()
+        );
+        assert_eq!(surface[pos2(4, 0)].underline, Underline::Dashed);
+        round_trip(&surface);
+    }
+
+    #[test]
+    fn cursor_forward() {
+        let surface = Surface::from_ansi("\x1b[Ca\x1b[0Cb\x1b[3Cc");
+        assert_eq!(surface.size(), vec2(8, 1));
+        assert_eq!(surface[pos2(1, 0)].char, 'a');
+        assert_eq!(surface[pos2(3, 0)].char, 'b');
+        assert_eq!(surface[pos2(7, 0)].char, 'c');
+
+        // skipping past the end of a line still widens the surface
+        let surface = Surface::from_ansi("ab\x1b[2C\ncd");
+        assert_eq!(surface.size(), vec2(4, 2));
+        round_trip(&surface);
+    }
+
+    #[test]
+    fn cursor_forward_is_clamped() {
+        let max = Surface::MAX_ANSI_SIZE;
+
+        let surface = Surface::from_ansi("\x1b[65535C\x1b[65535Cx");
+        assert_eq!(surface.size(), vec2(max.x, 1));
+        assert!((0..max.x).all(|x| surface[pos2(x, 0)].char == ' '));
+
+        // a count that doesn't fit is as far as possible
+        let surface = Surface::from_ansi("\x1b[99999999Cx");
+        assert_eq!(surface.size(), vec2(max.x, 1));
+    }
+
+    #[test]
+    fn size_is_limited() {
+        let max = Surface::MAX_ANSI_SIZE;
+
+        let line = "x".repeat(max.x as usize + 10);
+        let surface = Surface::from_ansi(&line);
+        assert_eq!(surface.size(), vec2(max.x, 1));
+
+        let tabs = "\t".repeat(max.x as usize);
+        assert_eq!(Surface::from_ansi(&tabs).size(), vec2(max.x, 1));
+
+        let rows = "a\n".repeat(max.y as usize + 10);
+        assert_eq!(Surface::from_ansi(&rows).size(), vec2(1, max.y));
+    }
+
+    #[test]
+    fn other_sequences_are_ignored() {
+        let surface =
+            Surface::from_ansi("\x1b]0;title\x07a\x1b]8;;uri\x1b\\b\x1b[2Jc\x1a\x1b[31md");
+        assert_eq!(surface.size(), vec2(3, 1));
+        assert_eq!(surface[pos2(2, 0)].char, 'c');
+    }
+
+    #[test]
+    fn wide_graphemes_round_trip() {
+        let surface = Surface::from_ansi("\x1b[31m漢字\x1b[0m e\u{301}");
+        assert_eq!(surface.size(), vec2(6, 1));
+        assert!(surface[pos2(1, 0)].is_continuation());
+        round_trip(&surface);
+    }
+
+    #[test]
+    fn tall_files() {
+        // more cells than fit in a u16
+        let line = format!("\x1b[31m{}\x1b[0m\n", "x".repeat(80));
+        let surface = Surface::from_ansi(&line.repeat(900));
+        assert_eq!(surface.size(), vec2(80, 900));
+        assert_eq!(surface[pos2(79, 899)].char, 'x');
+        assert_eq!(surface[pos2(79, 899)].fg, Rgb::from_ansi256(1).into());
+        round_trip(&surface);
+    }
+}
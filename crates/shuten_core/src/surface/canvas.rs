@@ -1,43 +1,126 @@
-use super::{pos_to_index, Surface};
+use super::{Connectivity, Hyperlink, Link, Surface};
 use crate::{
-    geom::{pos2, Pos2, Rect},
-    style::Color,
+    border::{self, Arms, Border},
+    geom::{
+        gradient::{Fill, FillTarget},
+        pos2, Offset, Pos2, Rect,
+    },
+    shape::Shape,
+    style::{Color, Rgb},
+    text::Text,
     Cell,
 };
 
 /// Canvas allows you to `paint` to a [`Surface`]
+///
+/// Positions are local to the canvas, `pos2(0, 0)` is always the left-top of its [`area`](Canvas::area)
+///
+/// Anything drawn outside of the canvas, or outside of its current [clip](Canvas::push_clip), is clipped
 pub struct Canvas<'a> {
     surface: &'a mut Surface,
     rect: Rect,
+    clip: Rect,
+    clips: Vec<Rect>,
 }
 
 impl<'a> Canvas<'a> {
     /// Create a new Canvas with the provided [`Rect`] and target [`Surface`]
+    ///
+    /// The [`Rect`] is in the coordinates of the [`Surface`], and it becomes the origin of this canvas
     pub fn new(rect: Rect, surface: &'a mut Surface) -> Self {
-        Self { surface, rect }
+        Self {
+            surface,
+            rect,
+            clip: rect,
+            clips: Vec::new(),
+        }
+    }
+
+    /// Get the [`Rect`] of this canvas, in local coordinates
+    ///
+    /// This always starts at `pos2(0, 0)`
+    pub fn area(&self) -> Rect {
+        Rect::from_min_size(Pos2::ZERO, self.rect.size())
     }
 
-    /// Get the [`Rect`] of this canvas
-    pub const fn area(&self) -> Rect {
+    /// Get the [`Rect`] of this canvas, in the coordinates of its [`Surface`]
+    pub const fn surface_area(&self) -> Rect {
         self.rect
     }
 
-    /// Create this canvas to a new (smaller) [`Rect`]
+    /// Crop this canvas to a new (smaller) [`Rect`], in local coordinates
     ///
-    /// This allows you to 'localize' positions into a new rect
+    /// The cropped canvas has its own local coordinates, its `pos2(0, 0)` is the left-top of this [`Rect`]
     ///
-    /// The [`Canvas::area`] of this cropped canvas will be this [`Rect`]
+    /// The cropped canvas is clipped to this canvas, including its current [clip](Canvas::push_clip)
     ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2, Rect};
+    /// use shuten_core::{Cell, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(10, 5)));
+    /// let mut canvas = context.canvas();
+    ///
+    /// let mut widget = canvas.crop(Rect::from_min_size(pos2(3, 2), vec2(4, 2)));
+    /// assert_eq!(widget.area(), rect(vec2(4, 2)));
+    /// widget.put(pos2(0, 0), Cell::new('@'));
+    /// // this is outside of the cropped canvas
+    /// widget.put(pos2(4, 0), Cell::new('!'));
+    ///
+    /// assert_eq!(context.surface()[pos2(3, 2)].char, '@');
+    /// assert_eq!(context.surface()[pos2(7, 2)].char, ' ');
+    /// ```
     pub fn crop<'b>(&'b mut self, rect: Rect) -> Canvas<'b>
     where
         Self: 'b,
     {
+        let rect = self.to_surface_rect(rect);
         Canvas {
             surface: self.surface,
-            rect: Rect::from_min_size(rect.left_top(), rect.size()),
+            rect,
+            clip: self.clip.intersect(rect),
+            clips: Vec::new(),
+        }
+    }
+
+    /// Push a clip [`Rect`], in local coordinates
+    ///
+    /// Until it is [popped](Canvas::pop_clip), only things drawn inside of this [`Rect`] (and any previous clips) are drawn
+    pub fn push_clip(&mut self, rect: Rect) {
+        let rect = self.to_surface_rect(rect);
+        self.clips.push(self.clip);
+        self.clip = self.clip.intersect(rect);
+    }
+
+    /// Pop the last clip [`Rect`] that was [pushed](Canvas::push_clip)
+    ///
+    /// This does nothing if there aren't any pushed clips, so a [cropped](Canvas::crop) canvas can't escape its parent's clip
+    pub fn pop_clip(&mut self) {
+        if let Some(clip) = self.clips.pop() {
+            self.clip = clip;
         }
     }
 
+    /// Get the current clip [`Rect`], in local coordinates
+    pub fn clip(&self) -> Rect {
+        let origin = self.rect.left_top();
+        Rect::from_min_size(self.clip.left_top() - origin, self.clip.size())
+    }
+
+    /// Translate a local [`point`](Pos2) to the [`Surface`], if its drawable by this canvas
+    fn to_surface(&self, pos: Pos2) -> Option<Pos2> {
+        let origin = self.rect.left_top();
+        let pos = pos2(origin.x.checked_add(pos.x)?, origin.y.checked_add(pos.y)?);
+        (self.clip.contains(pos) && self.surface.contains(pos)).then_some(pos)
+    }
+
+    fn to_surface_rect(&self, rect: Rect) -> Rect {
+        Rect::from_min_size(
+            self.rect.left_top() + rect.left_top().to_vec2(),
+            rect.size(),
+        )
+    }
+
     /// Erase this canvas
     pub fn erase(&mut self) {
         self.erase_rect(self.area())
@@ -48,30 +131,180 @@ impl<'a> Canvas<'a> {
         self.rect(rect, Cell::RESET)
     }
 
-    // TODO should this be relative to our rect?
     /// Put this [`Cell`] at [`point`](Pos2)
+    ///
+    /// Wide cells also occupy the next column. If there isn't room for it, a blank is put instead.
+    ///
+    /// Any wide cell that is partially overwritten has its other half cleared
+    ///
+    /// [`Color::Blend`] and [`Color::Reuse`] colors are composited over the existing [`Cell`]'s colors
+    ///
+    /// A [merged](Cell::add_attr) attribute is resolved against the existing [`Cell`]'s attribute
     pub fn put(&mut self, pos: Pos2, mut cell: Cell) {
-        if !self.area().contains(pos) || !self.surface.contains(pos) {
+        let Some(at) = self.to_surface(pos) else {
             return;
+        };
+        cell.fg = cell.fg.composite_over(self.surface[at].fg, Rgb::DEFAULT_FG);
+        cell.bg = cell.bg.composite(self.surface[at].bg);
+        cell.underline_color = cell
+            .underline_color
+            .composite_over(self.surface[at].underline_color, Rgb::DEFAULT_FG);
+        cell.attr = cell.attr.composite(self.surface[at].attr);
+
+        let next = pos
+            .x
+            .checked_add(1)
+            .and_then(|x| self.to_surface(pos2(x, pos.y)));
+        let wide = cell.width() > 1;
+        if cell.is_continuation() || (wide && next.is_none()) {
+            cell = cell.char(' ');
         }
-        if cell.bg == Color::Reuse {
-            cell.bg = self.surface[pos].bg;
+
+        self.surface.clear_wide(at);
+        self.surface[at] = cell;
+
+        if let Some(next) = next.filter(|_| wide) {
+            self.surface.clear_wide(next);
+            self.surface[next] = Cell {
+                char: Cell::CONTINUATION.char,
+                grapheme: None,
+                ..cell
+            };
         }
-        if cell.fg == Color::Reuse {
-            cell.fg = self.surface[pos].fg;
+    }
+
+    /// Put this grapheme cluster at [`point`](Pos2), using the colors and attribute from the [`Cell`]
+    ///
+    /// This should be a single grapheme cluster, such as `e\u{301}`, `❤️` or `🇯🇵`
+    pub fn put_grapheme(&mut self, pos: Pos2, grapheme: &str, cell: Cell) {
+        let mut chars = grapheme.chars();
+        let Some(first) = chars.next() else { return };
+
+        let mut cell = cell.char(first);
+        if chars.next().is_some() {
+            cell.grapheme = Some(self.surface.intern(grapheme));
         }
-        self.surface[pos] = cell;
+        self.put(pos, cell)
     }
 
-    /// Get the [`Cell`] at this [`point`](Pos2)
-    pub fn get_mut(&mut self, pos: Pos2) -> Option<&mut Cell> {
-        if !self.area().contains(pos) || !self.surface.contains(pos) {
-            return None;
+    /// Intern a hyperlink on the underlying [`Surface`], so it can be used by a [`Cell`]
+    ///
+    /// Terminals that support `OSC 8` make the cells with this link clickable
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2};
+    /// use shuten_core::{Cell, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(10, 1)));
+    /// let mut canvas = context.canvas();
+    /// let link = canvas.link("https://example.com", None);
+    /// for (x, char) in (0..).zip("example".chars()) {
+    ///     canvas.put(pos2(x, 0), Cell::new(char).link(link));
+    /// }
+    ///
+    /// let surface = context.surface();
+    /// let target = surface.link(&surface[pos2(0, 0)]).unwrap();
+    /// assert_eq!(target.uri, "https://example.com");
+    /// ```
+    pub fn link(&mut self, uri: &str, id: Option<&str>) -> Link {
+        self.surface.intern_link(uri, id)
+    }
+
+    /// Copy the `source` [`Rect`] of a [`Surface`] onto this canvas, with its left-top at `dest`
+    ///
+    /// - `source` is clipped to the [`Surface`]
+    /// - `dest` can be negative, anything outside of this canvas is clipped
+    /// - [transparent](Cell::is_transparent) cells are skipped, and [`Color::Reuse`] and [`Color::Blend`] colors are composited
+    ///
+    /// This allows expensive drawing to be cached in an offscreen [`Surface`]
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{offset, pos2, rect, vec2, Rect};
+    /// use shuten_core::{Cell, Context, Surface};
+    ///
+    /// let mut logo = Surface::new(vec2(4, 2));
+    /// logo.canvas().fill(0xFF0000);
+    /// logo.canvas().put(pos2(1, 1), Cell::new('@'));
+    ///
+    /// let mut context = Context::new(rect(vec2(10, 5)));
+    /// let mut canvas = context.canvas();
+    /// canvas.blit(&logo, logo.rect(), offset(-1, 3));
+    /// assert_eq!(context.surface()[pos2(0, 4)].char, '@');
+    /// ```
+    pub fn blit(&mut self, surface: &Surface, source: Rect, dest: Offset) {
+        self.blit_with(surface, source, dest, |cell| cell)
+    }
+
+    /// [Blit](Canvas::blit) a [`Surface`], mapping each [`Cell`] before its put
+    pub(crate) fn blit_with(
+        &mut self,
+        surface: &Surface,
+        source: Rect,
+        dest: Offset,
+        mut map: impl FnMut(Cell) -> Cell,
+    ) {
+        let source = source.intersect(surface.rect());
+        let (left, top) = (source.left(), source.top());
+
+        for y in top..top + source.height() {
+            for x in left..left + source.width() {
+                let cell = &surface[pos2(x, y)];
+                // the leading half of a wide cell puts its continuation
+                if cell.is_transparent() || cell.is_continuation() {
+                    continue;
+                }
+
+                let dx = dest.x.saturating_add(i32::from(x - left));
+                let dy = dest.y.saturating_add(i32::from(y - top));
+                let (Ok(dx), Ok(dy)) = (u16::try_from(dx), u16::try_from(dy)) else {
+                    continue;
+                };
+
+                let cell = self.surface.adopt(surface, *cell);
+                self.put(pos2(dx, dy), map(cell));
+            }
+        }
+    }
+
+    /// Draw [`Text`] inside of this [`Rect`]
+    ///
+    /// The text is wrapped, aligned and truncated to fit the [`Rect`]
+    ///
+    /// This returns the [`Rect`] that the text actually used
+    pub fn text<'t>(&mut self, rect: Rect, text: impl Into<Text<'t>>) -> Rect {
+        let mut text = text.into();
+        if let Some(Hyperlink { uri, id }) = text.link {
+            text.style.link = Some(self.link(uri, id));
+        }
+        let lines = text.layout(rect.size());
+
+        let height = lines.len() as u16;
+        let top = rect.top() + text.align.y.offset(height, rect.height());
+
+        let (mut min, mut max) = (u16::MAX, u16::MIN);
+        for (y, line) in (top..).zip(&lines) {
+            let left = rect.left() + text.align.x.offset(line.width(), rect.width());
+            let mut x = left;
+            for &(grapheme, width) in &line.graphemes {
+                self.put_grapheme(pos2(x, y), grapheme, text.style);
+                x += width;
+            }
+            if line.width() > 0 {
+                (min, max) = (min.min(left), max.max(x));
+            }
         }
 
-        self.surface
-            .cells
-            .get_mut(pos_to_index(pos, self.surface.size.x))
+        if min > max {
+            min = rect.left() + text.align.x.offset(0, rect.width());
+            max = min;
+        }
+        Rect::from_min_max(pos2(min, top), pos2(max, top + height))
+    }
+
+    /// Get the [`Cell`] at this [`point`](Pos2)
+    pub fn get_mut(&mut self, pos: Pos2) -> Option<&mut Cell> {
+        let pos = self.to_surface(pos)?;
+        Some(&mut self.surface[pos])
     }
 
     /// Fill the entire canvas with the provided [`Color`]
@@ -84,6 +317,135 @@ impl<'a> Canvas<'a> {
         self.rect(rect, Cell::EMPTY.bg(bg))
     }
 
+    /// Get the connected region of cells, starting at `start`, that match the predicate
+    ///
+    /// The region doesn't leave this canvas, or its current [clip](Canvas::push_clip). Positions are in local coordinates, in breadth-first order
+    pub fn region(
+        &self,
+        start: Pos2,
+        connectivity: Connectivity,
+        predicate: impl FnMut(&Cell) -> bool,
+    ) -> Vec<Pos2> {
+        let Some(start) = self.to_surface(start) else {
+            return Vec::new();
+        };
+
+        let origin = self.rect.left_top().to_vec2();
+        let region = self
+            .surface
+            .region_in(self.clip, start, connectivity, predicate);
+        region.into_iter().map(|pos| pos - origin).collect()
+    }
+
+    /// Replace the connected region of cells, starting at `start`, that match the predicate with the provided [`Cell`]
+    ///
+    /// This is a _bucket fill_. See [`Canvas::region`]
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2};
+    /// use shuten_core::{Cell, Connectivity, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(5, 5)));
+    /// let mut canvas = context.canvas();
+    /// canvas.line(pos2(0, 4), pos2(4, 0), Cell::new('/'));
+    ///
+    /// // the diagonal line only blocks 4-connected fills
+    /// let start = canvas.get_mut(pos2(0, 0)).copied().unwrap();
+    /// canvas.flood_fill(pos2(0, 0), Connectivity::Four, |cell| cell.char == start.char, Cell::new('.'));
+    /// assert_eq!(context.surface()[pos2(4, 4)].char, ' ');
+    ///
+    /// let mut canvas = context.canvas();
+    /// canvas.flood_fill(pos2(0, 0), Connectivity::Eight, |cell| cell.char != '/', Cell::new('.'));
+    /// assert_eq!(context.surface()[pos2(4, 4)].char, '.');
+    /// ```
+    pub fn flood_fill(
+        &mut self,
+        start: Pos2,
+        connectivity: Connectivity,
+        predicate: impl FnMut(&Cell) -> bool,
+        cell: Cell,
+    ) {
+        for pos in self.region(start, connectivity, predicate) {
+            self.put(pos, cell)
+        }
+    }
+
+    /// Composite the [`Color`] over the colors in the specified [`Rect`], keeping the existing characters
+    ///
+    /// This is useful for dimming a backdrop with a [`Color::Blend`]
+    pub fn overlay(&mut self, rect: Rect, color: impl Into<Color>) {
+        let color = color.into();
+        for pos in rect.indices() {
+            if let Some(cell) = self.get_mut(pos) {
+                cell.fg = color.composite_over(cell.fg, Rgb::DEFAULT_FG);
+                cell.bg = color.composite(cell.bg);
+            }
+        }
+    }
+
+    /// Fill the specified [`Rect`] with a gradient [`Fill`]
+    ///
+    /// The existing characters are kept, unless the fill is [dithered](Fill::dither) into the foreground
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{gradient::Fill, pos2, rect, vec2};
+    /// use shuten_core::{style::{Color, Rgb}, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(5, 1)));
+    /// let mut canvas = context.canvas();
+    /// let fill = Fill::linear([Rgb::new(0, 0, 0), Rgb::new(255, 255, 255)], 0.0);
+    /// canvas.fill_gradient(canvas.area(), &fill);
+    ///
+    /// assert_eq!(context.surface()[pos2(2, 0)].bg, Color::Rgb(Rgb::new(128, 128, 128)));
+    /// ```
+    pub fn fill_gradient(&mut self, rect: Rect, fill: &Fill) {
+        const SHADES: [char; 4] = [' ', '░', '▒', '▓'];
+
+        for pos in rect.indices() {
+            let t = fill.t(rect, pos);
+            if let Some(levels) = fill.dither_levels() {
+                let steps = f32::from(levels - 1);
+                let index = (t * steps).floor().min(steps - 1.0);
+                let shade = ((t * steps - index) * SHADES.len() as f32).round() as usize;
+                let (index, shade) = match shade {
+                    shade if shade < SHADES.len() => (index, shade),
+                    _ => (index + 1.0, 0),
+                };
+
+                let current = fill.ramp.at(index / steps);
+                let next = fill.ramp.at((index + 1.0).min(steps) / steps);
+                match fill.target {
+                    FillTarget::Both => {
+                        self.put(pos, Cell::new(SHADES[shade]).fg(next).bg(current));
+                    }
+                    // without the background, the current color has to be a full block
+                    FillTarget::Fg if shade == 0 => {
+                        self.put(pos, Cell::new('█').fg(current).bg(Color::Reuse));
+                    }
+                    FillTarget::Fg => {
+                        self.put(pos, Cell::new(SHADES[shade]).fg(next).bg(Color::Reuse));
+                    }
+                    FillTarget::Bg => {
+                        if let Some(cell) = self.get_mut(pos) {
+                            cell.bg = fill.ramp.at((t * steps).round() / steps).into();
+                        }
+                    }
+                }
+                continue;
+            }
+
+            let Some(cell) = self.get_mut(pos) else {
+                continue;
+            };
+            let color = fill.ramp.at(t).into();
+            match fill.target {
+                FillTarget::Fg => cell.fg = color,
+                FillTarget::Bg => cell.bg = color,
+                FillTarget::Both => (cell.fg, cell.bg) = (color, color),
+            }
+        }
+    }
+
     /// Fill the specified [`Rect`] with the provided [`Cell`]
     pub fn rect(&mut self, rect: Rect, cell: Cell) {
         for pos in rect.indices() {
@@ -91,29 +453,136 @@ impl<'a> Canvas<'a> {
         }
     }
 
-    // TODO this
-    #[allow(dead_code)]
-    fn line(&mut self, start: Pos2, end: Pos2, cell: Cell) {
-        let (start, end) = (start.min(end), start.max(end));
+    /// Draw a line of [`Cell`]s from `start` to `end` (inclusive)
+    pub fn line(&mut self, start: Pos2, end: Pos2, cell: Cell) {
+        for pos in start.line_to(end) {
+            self.put(pos, cell)
+        }
+    }
+
+    /// Draw connected lines of [`Cell`]s through each of the `points`
+    pub fn polyline(&mut self, points: impl IntoIterator<Item = Pos2>, cell: Cell) {
+        let mut points = points.into_iter();
+        let Some(mut start) = points.next() else {
+            return;
+        };
 
-        let vertical = start.y..end.y;
-        let horizontal = start.x..end.x;
+        self.put(start, cell);
+        for end in points {
+            // the joint was already drawn by the previous segment
+            for pos in start.line_to(end).skip(1) {
+                self.put(pos, cell)
+            }
+            start = end;
+        }
+    }
 
-        match () {
-            _ if vertical.is_empty() => {
-                for x in start.x..=end.x {
-                    self.put(pos2(x, start.y), cell)
-                }
+    /// Draw the outline of the [`Rect`] with the provided [`Cell`]
+    pub fn stroke_rect(&mut self, rect: Rect, cell: Cell) {
+        if rect.width() == 0 || rect.height() == 0 {
+            return;
+        }
+
+        let (left, right) = (rect.left(), rect.right());
+        let (top, bottom) = (rect.top(), rect.bottom());
+
+        for x in left..=right {
+            self.put(pos2(x, top), cell);
+            if bottom != top {
+                self.put(pos2(x, bottom), cell);
+            }
+        }
+
+        for y in top + 1..bottom {
+            self.put(pos2(left, y), cell);
+            if right != left {
+                self.put(pos2(right, y), cell);
             }
-            _ if horizontal.is_empty() => {
-                for y in start.y..=end.y {
-                    self.put(pos2(start.x, y), cell)
+        }
+    }
+
+    /// Fill the [`Shape`] with the provided [`Cell`]
+    ///
+    /// Only the part of the shape inside of the current [clip](Canvas::clip) is rasterized
+    pub fn fill_shape(&mut self, shape: &Shape, cell: Cell) {
+        for pos in shape.fill_in(self.clip()) {
+            self.put(pos, cell)
+        }
+    }
+
+    /// Draw the outline of the [`Shape`] with the provided [`Cell`]
+    pub fn stroke_shape(&mut self, shape: &Shape, cell: Cell) {
+        for pos in shape.stroke_in(self.clip()) {
+            self.put(pos, cell)
+        }
+    }
+
+    /// Draw a [`Border`] around the edges of the [`Rect`]
+    ///
+    /// Any borders that this meets or crosses are merged into junctions
+    pub fn border(&mut self, rect: Rect, border: Border) {
+        if rect.width() == 0 || rect.height() == 0 {
+            return;
+        }
+
+        let (left, right) = (rect.left(), rect.right());
+        let (top, bottom) = (rect.top(), rect.bottom());
+
+        for y in top..=bottom {
+            for x in left..=right {
+                let (on_left, on_right) = (x == left, x == right);
+                let (on_top, on_bottom) = (y == top, y == bottom);
+                if !(on_left || on_right || on_top || on_bottom) {
+                    continue;
                 }
+
+                let arms = Arms::new(
+                    border.line,
+                    (on_left || on_right) && !on_top,
+                    (on_top || on_bottom) && !on_right,
+                    (on_left || on_right) && !on_bottom,
+                    (on_top || on_bottom) && !on_left,
+                );
+                self.put_border(pos2(x, y), arms, border);
+            }
+        }
+    }
+
+    /// Draw a horizontal or vertical [`Border`] line from `start` to `end` (inclusive)
+    ///
+    /// This is useful for dividers, any borders that this meets or crosses are merged into junctions
+    ///
+    /// Diagonal lines are not drawn
+    pub fn border_line(&mut self, start: Pos2, end: Pos2, border: Border) {
+        let (start, end) = (start.min(end), start.max(end));
+        if start.y == end.y {
+            for x in start.x..=end.x {
+                let arms = Arms::new(border.line, false, x != end.x, false, x != start.x);
+                self.put_border(pos2(x, start.y), arms, border);
+            }
+        } else if start.x == end.x {
+            for y in start.y..=end.y {
+                let arms = Arms::new(border.line, y != start.y, false, y != end.y, false);
+                self.put_border(pos2(start.x, y), arms, border);
             }
-            _ => {}
         }
     }
 
+    fn put_border(&mut self, pos: Pos2, arms: Arms, border: Border) {
+        let Some(at) = self.to_surface(pos) else {
+            return;
+        };
+
+        let existing = self.surface[at];
+        let existing = match existing.grapheme {
+            Some(..) => ' ',
+            None => existing.char,
+        };
+
+        let char = border::merge(existing, arms, border.line);
+        self.put(pos, border.style.char(char))
+    }
+
     /// Tries to set the [`foreground`](Color) and [`background`](Color) at the specified [`point`](Pos2)
     pub fn set_color(&mut self, pos: Pos2, fg: impl Into<Color>, bg: impl Into<Color>) {
         if let Some(cell) = self.get_mut(pos) {
@@ -122,3 +591,53 @@ impl<'a> Canvas<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::vec2;
+
+    #[test]
+    fn dither_with_too_few_levels() {
+        let black_white = [Rgb::new(0, 0, 0), Rgb::new(255, 255, 255)];
+        for levels in [0, 1, 2] {
+            let mut surface = Surface::new(vec2(4, 1));
+            let fill = Fill::linear(black_white, 0.0).both().dither(levels);
+            let rect = surface.rect();
+            surface.canvas().fill_gradient(rect, &fill);
+
+            assert_eq!(surface[pos2(0, 0)].bg, Color::Rgb(black_white[0]));
+            assert_eq!(surface[pos2(3, 0)].fg, Color::Rgb(black_white[1]));
+        }
+    }
+
+    #[test]
+    fn dither_keeps_the_other_color() {
+        let black_white = [Rgb::new(0, 0, 0), Rgb::new(255, 255, 255)];
+        let red = Rgb::new(255, 0, 0);
+        let blue = Rgb::new(0, 0, 255);
+
+        let mut surface = Surface::new(vec2(4, 1));
+        let rect = surface.rect();
+        let mut canvas = surface.canvas();
+        canvas.fill_rect(rect, red);
+        canvas.fill_gradient(rect, &Fill::linear(black_white, 0.0).fg().dither(4));
+        for pos in (0..4).map(|x| pos2(x, 0)) {
+            assert_eq!(surface[pos].bg, Color::Rgb(red));
+            assert_ne!(surface[pos].char, ' ');
+        }
+
+        let mut surface = Surface::new(vec2(4, 1));
+        let rect = surface.rect();
+        let mut canvas = surface.canvas();
+        canvas.text(rect, "abcd");
+        canvas.set_color(pos2(0, 0), blue, Color::Reset);
+        canvas.fill_gradient(rect, &Fill::linear(black_white, 0.0).bg().dither(4));
+        assert_eq!(surface[pos2(0, 0)].fg, Color::Rgb(blue));
+        assert_eq!(surface[pos2(0, 0)].bg, Color::Rgb(black_white[0]));
+        assert_eq!(surface[pos2(3, 0)].bg, Color::Rgb(black_white[1]));
+        for (x, ch) in (0..4).zip("abcd".chars()) {
+            assert_eq!(surface[pos2(x, 0)].char, ch);
+        }
+    }
+}
@@ -1,18 +1,112 @@
-use crate::style::{Attribute, Color};
+use unicode_width::UnicodeWidthChar as _;
 
+use super::{Grapheme, Link};
+use crate::style::{Attribute, Color, Underline};
+
+/// The attribute of a [`Cell`]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum CellAttr {
+    /// Use this [`Attribute`]
     Attr(Attribute),
+    /// Reset the attribute
     Reset,
+    /// Merge with the attribute beneath, adding and then removing flags
+    ///
+    /// This is resolved by [`Canvas::put`](crate::Canvas::put), like [`Color::Reuse`]
+    Merge {
+        /// The flags to add
+        add: Attribute,
+        /// The flags to remove
+        remove: Attribute,
+    },
+}
+
+impl CellAttr {
+    /// Keep the attribute beneath
+    pub const INHERIT: Self = Self::Merge {
+        add: Attribute(0),
+        remove: Attribute(0),
+    };
+
+    /// Composite this attribute over the `base` attribute
+    ///
+    /// - A [`CellAttr::Merge`] over an [`Attribute`] produces the merged [`Attribute`]
+    /// - A [`CellAttr::Merge`] over another [`CellAttr::Merge`] combines them, so it can be composited later
+    ///
+    /// Any other attribute is returned as-is
+    pub fn composite(self, base: Self) -> Self {
+        match (self, base) {
+            (Self::Merge { add, remove }, Self::Merge { add: a, remove: r }) => Self::Merge {
+                add: (a & !remove) | add,
+                remove: (r | remove) & !add,
+            },
+            (Self::Merge { add, remove }, base) => Self::Attr((base.flags() & !remove) | add),
+            (attr, _) => attr,
+        }
+    }
+
+    /// The flags of this attribute
+    ///
+    /// An unresolved [`CellAttr::Merge`] is treated as if it was over an empty attribute
+    pub const fn flags(self) -> Attribute {
+        match self {
+            Self::Attr(attr) => attr,
+            Self::Reset => Attribute(0),
+            Self::Merge { add, .. } => add,
+        }
+    }
+
+    /// Add flags to this attribute
+    ///
+    /// [`CellAttr::Reset`] becomes a [`CellAttr::Merge`]
+    fn add(self, attr: Attribute) -> Self {
+        match self {
+            Self::Attr(flags) => Self::Attr(flags | attr),
+            Self::Reset => Self::INHERIT.add(attr),
+            Self::Merge { add, remove } => Self::Merge {
+                add: add | attr,
+                remove: remove & !attr,
+            },
+        }
+    }
+
+    /// Remove flags from this attribute
+    ///
+    /// [`CellAttr::Reset`] becomes a [`CellAttr::Merge`]
+    fn remove(self, attr: Attribute) -> Self {
+        match self {
+            Self::Attr(flags) => Self::Attr(flags & !attr),
+            Self::Reset => Self::INHERIT.remove(attr),
+            Self::Merge { add, remove } => Self::Merge {
+                add: add & !attr,
+                remove: remove | attr,
+            },
+        }
+    }
 }
 
 /// Cells are written to the [`Surface`](crate::Surface) and interpreted by a [`Context`](crate::Context) and used by a [`Canvas`](crate::Canvas)
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Cell {
+    /// The character for this cell
+    ///
+    /// If this cell has a [`grapheme`](Self::grapheme), this is its first character
     pub char: char,
+    /// A grapheme cluster for this cell, interned by a [`Surface`](crate::Surface)
+    ///
+    /// This is used for characters made up of multiple code points, see [`Canvas::put_grapheme`](crate::Canvas::put_grapheme)
+    pub grapheme: Option<Grapheme>,
+    /// A hyperlink for this cell, interned by a [`Surface`](crate::Surface)
+    ///
+    /// See [`Canvas::link`](crate::Canvas::link)
+    pub link: Option<Link>,
     pub fg: Color,
     pub bg: Color,
     pub attr: CellAttr,
+    /// How the underline is drawn, if the [`attr`](Self::attr) has [`Attribute::UNDERLINE`]
+    pub underline: Underline,
+    /// The color of the underline, [`Color::Reset`] uses the foreground
+    pub underline_color: Color,
 }
 
 impl Default for Cell {
@@ -30,19 +124,65 @@ impl Cell {
         bg: Color::Reset,
         ..Self::new(' ')
     };
+    /// A `Transparent` cell, this is skipped when drawing and compositing
+    pub const TRANSPARENT: Self = Self {
+        fg: Color::Reuse,
+        bg: Color::Reuse,
+        attr: CellAttr::INHERIT,
+        ..Self::new(' ')
+    };
+    /// The trailing half of a wide cell
+    ///
+    /// This is managed by the [`Canvas`](crate::Canvas), you shouldn't need to write it yourself
+    pub const CONTINUATION: Self = Self::new('\0');
 
     /// Create a new cell that reuses prior colors
     pub const fn new(char: char) -> Self {
         Self {
             char,
+            grapheme: None,
+            link: None,
             fg: Color::Reset,
             bg: Color::Reuse,
             attr: CellAttr::Reset,
+            underline: Underline::Single,
+            underline_color: Color::Reset,
         }
     }
 
+    /// Is this cell transparent?
+    ///
+    /// A cell that reuses both of its colors is transparent
+    pub fn is_transparent(&self) -> bool {
+        self.fg == Color::Reuse && self.bg == Color::Reuse
+    }
+
+    /// How many columns this cell occupies
+    ///
+    /// - Wide characters (e.g. `漢` or `🎉`) occupy `2` columns
+    /// - A [continuation](Self::CONTINUATION) occupies `0` columns
+    /// - Everything else occupies `1` column
+    pub fn width(&self) -> u16 {
+        if self.is_continuation() {
+            return 0;
+        }
+        if let Some(grapheme) = self.grapheme {
+            return grapheme.width();
+        }
+        self.char.width().unwrap_or(1).clamp(1, 2) as u16
+    }
+
+    /// Is this the trailing half of a wide cell?
+    pub const fn is_continuation(&self) -> bool {
+        self.char == Self::CONTINUATION.char && self.grapheme.is_none()
+    }
+
+    /// Set the character of this cell
+    ///
+    /// This replaces any [`grapheme`](Self::grapheme)
     pub fn char(mut self, char: char) -> Self {
         self.char = char;
+        self.grapheme = None;
         self
     }
 
@@ -65,6 +205,69 @@ impl Cell {
         self.attr = attr.into().map(CellAttr::Attr).unwrap_or(CellAttr::Reset);
         self
     }
+
+    /// Add flags to the attribute of this cell
+    ///
+    /// If the attribute was reset, the flags are merged with the attribute beneath when this cell is [put](crate::Canvas::put)
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2};
+    /// use shuten_core::style::Attribute;
+    /// use shuten_core::{Cell, Context};
+    ///
+    /// let mut context = Context::new(rect(vec2(10, 1)));
+    /// let mut canvas = context.canvas();
+    /// canvas.put(pos2(0, 0), Cell::new('a').attr(Attribute::BOLD));
+    /// // highlight the cell, without knowing what was drawn before
+    /// canvas.put(pos2(0, 0), Cell::new('b').add_attr(Attribute::REVERSE));
+    ///
+    /// let expected = Cell::EMPTY.attr(Attribute::BOLD | Attribute::REVERSE).attr;
+    /// assert_eq!(context.surface()[pos2(0, 0)].attr, expected);
+    /// ```
+    pub fn add_attr(mut self, attr: Attribute) -> Self {
+        self.attr = self.attr.add(attr);
+        self
+    }
+
+    /// Remove flags from the attribute of this cell
+    ///
+    /// If the attribute was reset, the flags are removed from the attribute beneath when this cell is [put](crate::Canvas::put)
+    pub fn remove_attr(mut self, attr: Attribute) -> Self {
+        self.attr = self.attr.remove(attr);
+        self
+    }
+
+    /// Underline this cell with a [style](Underline)
+    ///
+    /// This adds [`Attribute::UNDERLINE`] to the attribute of this cell
+    ///
+    /// ```rust
+    /// use shuten_core::{style::{Attribute, Underline}, Cell};
+    /// let cell = Cell::new('x').attr(Attribute::BOLD).underline(Underline::Curly).underline_color(0xFF0000);
+    /// assert_eq!(cell.attr, Cell::EMPTY.attr(Attribute::BOLD | Attribute::UNDERLINE).attr);
+    /// ```
+    pub fn underline(mut self, style: Underline) -> Self {
+        self.underline = style;
+        self.attr = match self.attr {
+            CellAttr::Reset => CellAttr::Attr(Attribute::UNDERLINE),
+            attr => attr.add(Attribute::UNDERLINE),
+        };
+        self
+    }
+
+    /// Set the color of the underline of this cell
+    pub fn underline_color(mut self, color: impl Into<Color>) -> Self {
+        self.underline_color = color.into();
+        self
+    }
+
+    /// Set the hyperlink of this cell
+    ///
+    /// If `none` is provided, then the cell isn't a link
+    pub fn link(mut self, link: impl Into<Option<Link>>) -> Self {
+        self.link = link.into();
+        self
+    }
 }
 
 impl Cell {
@@ -73,6 +276,11 @@ impl Cell {
         self
     }
 
+    pub const fn reuse_attr(mut self) -> Self {
+        self.attr = CellAttr::INHERIT;
+        self
+    }
+
     pub const fn reset_fg(mut self) -> Self {
         self.fg = Color::Reset;
         self
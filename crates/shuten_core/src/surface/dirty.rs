@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+/// The columns of a row that have been written to since the last frame
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Span {
+    start: u16,
+    end: u16,
+}
+
+impl Span {
+    pub(crate) const CLEAN: Self = Self {
+        start: u16::MAX,
+        end: u16::MIN,
+    };
+
+    pub(crate) const fn full(width: u16) -> Self {
+        Self {
+            start: 0,
+            end: width,
+        }
+    }
+
+    pub(crate) fn mark(&mut self, columns: Range<u16>) {
+        self.start = self.start.min(columns.start);
+        self.end = self.end.max(columns.end);
+    }
+
+    pub(crate) fn merge(&mut self, other: Self) {
+        if !other.is_clean() {
+            self.mark(other.columns())
+        }
+    }
+
+    pub(crate) const fn is_clean(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub(crate) const fn columns(&self) -> Range<u16> {
+        self.start..self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mark_extends_the_span() {
+        let mut span = Span::CLEAN;
+        assert!(span.is_clean());
+
+        span.mark(3..5);
+        assert_eq!(span.columns(), 3..5);
+
+        span.mark(8..9);
+        assert_eq!(span.columns(), 3..9);
+
+        span.mark(0..1);
+        assert_eq!(span.columns(), 0..9);
+        assert!(!span.is_clean());
+    }
+
+    #[test]
+    fn merge() {
+        let mut span = Span::CLEAN;
+        span.merge(Span::CLEAN);
+        assert!(span.is_clean());
+
+        let mut other = Span::CLEAN;
+        other.mark(4..6);
+        span.merge(other);
+        assert_eq!(span.columns(), 4..6);
+
+        span.merge(Span::full(2));
+        assert_eq!(span.columns(), 0..6);
+
+        // a clean span doesn't widen a dirty span
+        span.merge(Span::CLEAN);
+        assert_eq!(span.columns(), 0..6);
+    }
+}
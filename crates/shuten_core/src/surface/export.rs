@@ -0,0 +1,308 @@
+use std::fmt::Write as _;
+
+use super::{ansi::Style, Surface};
+use crate::{
+    geom::pos2,
+    style::{Rgb, Underline},
+};
+
+/// The foreground color used for [`Color::Reset`](crate::style::Color::Reset)
+pub(super) const DEFAULT_FG: Rgb = Rgb::DEFAULT_FG;
+/// The background color used for [`Color::Reset`](crate::style::Color::Reset)
+pub(super) const DEFAULT_BG: Rgb = Rgb::DEFAULT_BG;
+
+/// The size of a cell in an svg, in pixels
+const CELL_WIDTH: u32 = 10;
+const CELL_HEIGHT: u32 = 20;
+const FONT_SIZE: u32 = 16;
+
+impl Surface {
+    /// Write this [`Surface`] as a standalone HTML `<pre>` element
+    ///
+    /// Adjacent cells with the same style are merged into a single `<span>`, with an inline style
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::{pos2, vec2}, style::Attribute, Cell, Surface};
+    /// let mut surface = Surface::new(vec2(4, 1));
+    /// let mut canvas = surface.canvas();
+    /// canvas.put(pos2(0, 0), Cell::new('h').fg(0xFF0000).attr(Attribute::BOLD));
+    /// canvas.put(pos2(1, 0), Cell::new('i').fg(0xFF0000).attr(Attribute::BOLD));
+    ///
+    /// let html = surface.to_html();
+    /// assert!(html.contains(r#"<span style="color: #ff0000; font-weight: bold">hi</span>"#));
+    /// ```
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub fn to_html(&self) -> String {
+        let mut out = format!(
+            r#"<pre style="font-family: monospace; color: {DEFAULT_FG:x}; background-color: {DEFAULT_BG:x}">"#
+        );
+
+        for (y, row) in self.runs().into_iter().enumerate() {
+            if y > 0 {
+                out.push('\n');
+            }
+
+            for run in row {
+                let css = css(&run.style);
+                if css.is_empty() {
+                    escape(&run.text, &mut out);
+                    continue;
+                }
+                _ = write!(out, r#"<span style="{css}">"#);
+                escape(&run.text, &mut out);
+                out.push_str("</span>");
+            }
+        }
+
+        out.push_str("</pre>");
+        out
+    }
+
+    /// Write this [`Surface`] as a standalone SVG, laid out as a monospace text grid
+    ///
+    /// Adjacent cells with the same style are merged into a single `<text>` element, stretched to the width of its cells
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::{pos2, vec2}, Cell, Surface};
+    /// let mut surface = Surface::new(vec2(4, 2));
+    /// surface.canvas().put(pos2(3, 1), Cell::new('@').bg(0x0000FF));
+    ///
+    /// let svg = surface.to_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(r##"<rect x="30" y="20" width="10" height="20" fill="#0000ff"/>"##));
+    /// ```
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (
+            self.size.x as u32 * CELL_WIDTH,
+            self.size.y as u32 * CELL_HEIGHT,
+        );
+
+        let mut out = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="{FONT_SIZE}">"#
+        );
+        _ = write!(
+            out,
+            r#"<rect width="100%" height="100%" fill="{DEFAULT_BG:x}"/>"#
+        );
+
+        for (y, row) in (0..).zip(self.runs()) {
+            let top = y * CELL_HEIGHT;
+            for run in row {
+                let (fg, bg) = colors(&run.style);
+                let (x, width) = (run.x * CELL_WIDTH, run.width * CELL_WIDTH);
+
+                if bg != DEFAULT_BG {
+                    _ = write!(
+                        out,
+                        r#"<rect x="{x}" y="{top}" width="{width}" height="{CELL_HEIGHT}" fill="{bg:x}"/>"#
+                    );
+                }
+
+                let attr = run.style.attr;
+                if run.text.trim().is_empty() || attr.is_conceal() {
+                    continue;
+                }
+
+                let baseline = top + CELL_HEIGHT * 3 / 4;
+                _ = write!(
+                    out,
+                    r#"<text x="{x}" y="{baseline}" textLength="{width}" lengthAdjust="spacingAndGlyphs" xml:space="preserve" fill="{fg:x}""#
+                );
+                if attr.is_bold() {
+                    out.push_str(r#" font-weight="bold""#);
+                }
+                if attr.is_faint() {
+                    out.push_str(r#" fill-opacity="0.5""#);
+                }
+                if attr.is_italic() {
+                    out.push_str(r#" font-style="italic""#);
+                }
+                if let Some(decoration) = decoration(&run.style) {
+                    _ = write!(out, r#" text-decoration="{decoration}""#);
+                }
+                out.push('>');
+                escape(&run.text, &mut out);
+                out.push_str("</text>");
+            }
+        }
+
+        out.push_str("</svg>");
+        out
+    }
+
+    /// Merge each row into runs of cells with the same style
+    fn runs(&self) -> Vec<Vec<Run>> {
+        let mut rows = Vec::with_capacity(self.size.y as usize);
+        for y in 0..self.size.y {
+            let mut row = Vec::<Run>::new();
+            let mut current = Style::default();
+
+            for x in 0..self.size.x {
+                let cell = self[pos2(x, y)];
+                if cell.is_continuation() {
+                    continue;
+                }
+
+                current = current.update(&cell);
+                let width = cell.width() as u32;
+                let mut buf = [0; 4];
+                let text = match self.grapheme(&cell) {
+                    Some(grapheme) => grapheme,
+                    None => cell.char.encode_utf8(&mut buf),
+                };
+
+                match row.last_mut() {
+                    Some(run) if run.style == current => {
+                        run.text.push_str(text);
+                        run.width += width;
+                    }
+                    _ => row.push(Run {
+                        x: x as u32,
+                        width,
+                        text: text.to_string(),
+                        style: current,
+                    }),
+                }
+            }
+            rows.push(row);
+        }
+        rows
+    }
+}
+
+/// Adjacent cells that share a style
+struct Run {
+    x: u32,
+    width: u32,
+    text: String,
+    style: Style,
+}
+
+/// Resolve the colors of a style, swapping them if its reversed
+pub(super) fn colors(style: &Style) -> (Rgb, Rgb) {
+    let fg = style.fg.unwrap_or(DEFAULT_FG);
+    let bg = style.bg.unwrap_or(DEFAULT_BG);
+    match style.attr.is_reverse() {
+        true => (bg, fg),
+        false => (fg, bg),
+    }
+}
+
+/// The `text-decoration` of a style, including the underline style and color
+fn decoration(style: &Style) -> Option<String> {
+    let attr = style.attr;
+    let underline = match (attr.is_underline(), attr.is_double_underline()) {
+        (true, _) => Some(style.underline),
+        (false, true) => Some(Underline::Double),
+        (false, false) => None,
+    };
+
+    let lines = [
+        (underline.is_some(), "underline"),
+        (attr.is_overline(), "overline"),
+        (attr.is_strike_out(), "line-through"),
+    ];
+    let mut out = lines
+        .into_iter()
+        .filter_map(|(enabled, line)| enabled.then_some(line))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if out.is_empty() {
+        return None;
+    }
+
+    if let Some(underline) = underline {
+        out.push_str(match underline {
+            Underline::Single => "",
+            Underline::Double => " double",
+            Underline::Curly => " wavy",
+            Underline::Dotted => " dotted",
+            Underline::Dashed => " dashed",
+        });
+        if let Some(color) = style.underline_color {
+            _ = write!(out, " {color:x}");
+        }
+    }
+    Some(out)
+}
+
+/// Inline css for a style, this is empty for the default style
+fn css(style: &Style) -> String {
+    let mut out = String::new();
+    let mut push = |property: std::fmt::Arguments<'_>| {
+        if !out.is_empty() {
+            out.push_str("; ");
+        }
+        _ = out.write_fmt(property);
+    };
+
+    let attr = style.attr;
+    if attr.is_reverse() {
+        let (fg, bg) = colors(style);
+        push(format_args!("color: {fg:x}"));
+        push(format_args!("background-color: {bg:x}"));
+    } else {
+        if let Some(fg) = style.fg {
+            push(format_args!("color: {fg:x}"));
+        }
+        if let Some(bg) = style.bg {
+            push(format_args!("background-color: {bg:x}"));
+        }
+    }
+
+    if attr.is_bold() {
+        push(format_args!("font-weight: bold"));
+    }
+    if attr.is_faint() {
+        push(format_args!("opacity: 0.5"));
+    }
+    if attr.is_italic() {
+        push(format_args!("font-style: italic"));
+    }
+    if let Some(decoration) = decoration(style) {
+        push(format_args!("text-decoration: {decoration}"));
+    }
+    if attr.is_conceal() {
+        push(format_args!("color: transparent"));
+    }
+    out
+}
+
+/// Escape text for use in HTML and SVG
+fn escape(text: &str, out: &mut String) {
+    for char in text.chars() {
+        match char {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            // control characters aren't allowed in XML
+            '\t' => out.push(char),
+            '\0'..='\x1f' | '\x7f' => out.push(char::REPLACEMENT_CHARACTER),
+            char => out.push(char),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        geom::{pos2, vec2},
+        Cell, Surface,
+    };
+
+    #[test]
+    fn control_characters_are_replaced() {
+        let mut surface = Surface::new(vec2(4, 1));
+        let mut canvas = surface.canvas();
+        for (x, char) in ['\x1b', '\x07', '\x7f', 'a'].into_iter().enumerate() {
+            canvas.put(pos2(x as u16, 0), Cell::new(char));
+        }
+
+        for out in [surface.to_svg(), surface.to_html()] {
+            assert!(!out.contains(|c: char| c.is_control() && c != '\n'));
+            assert!(out.contains("\u{FFFD}\u{FFFD}\u{FFFD}a"));
+        }
+    }
+}
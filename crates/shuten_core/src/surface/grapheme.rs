@@ -0,0 +1,82 @@
+use std::{collections::HashMap, num::NonZeroU32, sync::Arc};
+
+use unicode_width::UnicodeWidthChar as _;
+
+/// A reference to a grapheme cluster interned by a [`Surface`](crate::Surface)
+///
+/// This is only valid for the [`Surface`](crate::Surface) that interned it
+///
+/// Unused graphemes are forgotten when the surface is filled, resized or the frame ends, so intern it each time it's drawn
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grapheme {
+    index: NonZeroU32,
+    wide: bool,
+}
+
+impl Grapheme {
+    /// How many columns this grapheme occupies
+    pub const fn width(&self) -> u16 {
+        1 + self.wide as u16
+    }
+}
+
+/// An arena of interned grapheme clusters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Graphemes {
+    strings: Vec<Arc<str>>,
+    lookup: HashMap<Arc<str>, Grapheme>,
+}
+
+// a `Surface` has to stay `Send`
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Graphemes>();
+};
+
+impl Graphemes {
+    pub(crate) fn intern(&mut self, grapheme: &str) -> Grapheme {
+        if let Some(&id) = self.lookup.get(grapheme) {
+            return id;
+        }
+
+        let string: Arc<str> = grapheme.into();
+        self.strings.push(string.clone());
+
+        let index = u32::try_from(self.strings.len())
+            .ok()
+            .and_then(NonZeroU32::new)
+            .expect("too many graphemes interned");
+
+        let id = Grapheme {
+            index,
+            wide: width(grapheme) > 1,
+        };
+        self.lookup.insert(string, id);
+        id
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub(crate) fn get(&self, id: Grapheme) -> Option<&str> {
+        self.strings.get(id.index.get() as usize - 1).map(|s| &**s)
+    }
+}
+
+/// How many columns this grapheme cluster occupies
+pub(crate) fn width(grapheme: &str) -> u16 {
+    const EMOJI_PRESENTATION: char = '\u{FE0F}';
+    const REGIONAL_INDICATORS: std::ops::RangeInclusive<char> = '\u{1F1E6}'..='\u{1F1FF}';
+
+    // terminals draw emoji presentation sequences and flags as wide
+    let wide = grapheme.contains(EMOJI_PRESENTATION)
+        || grapheme
+            .chars()
+            .filter(|c| REGIONAL_INDICATORS.contains(c))
+            .count()
+            == 2
+        || grapheme.chars().any(|c| c.width() == Some(2));
+
+    1 + wide as u16
+}
@@ -0,0 +1,131 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Write as _, num::NonZeroU32, sync::Arc};
+
+/// A reference to a hyperlink interned by a [`Surface`](crate::Surface)
+///
+/// This is only valid for the [`Surface`](crate::Surface) that interned it
+///
+/// Unused links are forgotten when the surface is filled, resized or the frame ends, so intern it each time it's drawn
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Link {
+    index: NonZeroU32,
+}
+
+/// The target of a [`Link`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hyperlink<'a> {
+    /// The URI that is opened
+    pub uri: &'a str,
+    /// An optional id, cells with the same id and URI are treated as a single link by the terminal
+    ///
+    /// This is useful when a link is split up, such as by wrapping
+    pub id: Option<&'a str>,
+}
+
+type Target = (Arc<str>, Option<Arc<str>>);
+
+/// An arena of interned hyperlinks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Links {
+    targets: Vec<Target>,
+    lookup: HashMap<Target, Link>,
+}
+
+// a `Surface` has to stay `Send`
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Links>();
+};
+
+impl Links {
+    pub(crate) fn intern(&mut self, uri: &str, id: Option<&str>) -> Link {
+        let (uri, id) = (
+            percent_encode(uri, b""),
+            id.map(|id| percent_encode(id, b";:")),
+        );
+        let target: Target = (uri.as_ref().into(), id.as_deref().map(Into::into));
+        if let Some(&link) = self.lookup.get(&target) {
+            return link;
+        }
+
+        self.targets.push(target.clone());
+
+        let index = u32::try_from(self.targets.len())
+            .ok()
+            .and_then(NonZeroU32::new)
+            .expect("too many links interned");
+
+        let link = Link { index };
+        self.lookup.insert(target, link);
+        link
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub(crate) fn get(&self, link: Link) -> Option<Hyperlink<'_>> {
+        let (uri, id) = self.targets.get(link.index.get() as usize - 1)?;
+        Some(Hyperlink {
+            uri,
+            id: id.as_deref(),
+        })
+    }
+}
+
+/// Percent-encode the bytes that aren't printable ASCII, and any of the `reserved` bytes
+///
+/// This keeps a hyperlink from ending its escape sequence early
+fn percent_encode<'a>(input: &'a str, reserved: &[u8]) -> Cow<'a, str> {
+    let encode = |byte: u8| !(0x20..=0x7E).contains(&byte) || reserved.contains(&byte);
+    if !input.bytes().any(encode) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match encode(byte) {
+            true => _ = write!(out, "%{byte:02X}"),
+            false => out.push(char::from(byte)),
+        }
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_are_encoded() {
+        let mut links = Links::default();
+        let link = links.intern("https://a.b/\x1b]52;c;\x07", Some("x;y:z\x1b"));
+        assert_eq!(
+            links.get(link),
+            Some(Hyperlink {
+                uri: "https://a.b/%1B]52;c;%07",
+                id: Some("x%3By%3Az%1B"),
+            })
+        );
+
+        // the encoded target is the same link
+        assert_eq!(
+            links.intern("https://a.b/%1B]52;c;%07", Some("x%3By%3Az%1B")),
+            link
+        );
+        assert_eq!(links.len(), 1);
+    }
+
+    #[test]
+    fn non_ascii_is_encoded() {
+        let mut links = Links::default();
+        let link = links.intern("https://example.com/caf\u{e9}", None);
+        let target = links.get(link).unwrap();
+        assert_eq!(target.uri, "https://example.com/caf%C3%A9");
+        assert_eq!(target.id, None);
+
+        let link = links.intern("https://example.com/?a=1;b=2", Some("plain"));
+        let target = links.get(link).unwrap();
+        assert_eq!(target.uri, "https://example.com/?a=1;b=2");
+        assert_eq!(target.id, Some("plain"));
+    }
+}
@@ -0,0 +1,122 @@
+use super::{
+    ansi::Style,
+    export::{colors, DEFAULT_BG},
+    Surface,
+};
+use crate::{geom::pos2, style::Underline};
+
+mod font;
+use font::{Bitmap, CELL_HEIGHT, CELL_WIDTH, OVERLINE, STRIKE_OUT, UNDERLINE};
+
+mod png;
+
+impl Surface {
+    /// Rasterize this [`Surface`] to a PNG, using an embedded bitmap font
+    ///
+    /// Each cell is `6x12` pixels. The font covers ASCII, box-drawing, block elements and braille patterns, anything else is drawn as a hollow box
+    ///
+    /// - Bold is emulated by drawing the glyph twice, one pixel apart
+    /// - Faint mixes the foreground with the background
+    /// - Underline, overline and strike-out draw a line across the cell, using the underline style and color
+    /// - Conceal only draws the background
+    /// - Reverse swaps the foreground and background
+    ///
+    /// This doesn't depend on any fonts or tools being installed, so the same surface always produces the same bytes
+    ///
+    /// ```rust
+    /// use shuten_core::{geom::{pos2, vec2}, Cell, Surface};
+    /// let mut surface = Surface::new(vec2(10, 2));
+    /// surface.canvas().put(pos2(0, 0), Cell::new('#').fg(0xFF0000));
+    ///
+    /// let png = surface.to_png();
+    /// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    /// assert_eq!(png, surface.to_png());
+    /// ```
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub fn to_png(&self) -> Vec<u8> {
+        let width = self.size.x as usize * CELL_WIDTH;
+        let height = self.size.y as usize * CELL_HEIGHT;
+        let mut pixels = vec![DEFAULT_BG; width * height];
+
+        for y in 0..self.size.y {
+            let mut current = Style::default();
+            for x in 0..self.size.x {
+                let cell = self[pos2(x, y)];
+                if cell.is_continuation() {
+                    continue;
+                }
+
+                current = current.update(&cell);
+                let (mut fg, bg) = colors(&current);
+                let attr = current.attr;
+                if attr.is_faint() {
+                    fg = fg.blend_flat(bg, 0.5);
+                }
+
+                let columns =
+                    (cell.width() as usize * CELL_WIDTH).min(width - x as usize * CELL_WIDTH);
+                let mut bitmap = match (cell.grapheme, cell.char) {
+                    _ if attr.is_conceal() => Bitmap::default(),
+                    (None, ' ') => Bitmap::default(),
+                    (None, char) => font::glyph(char).unwrap_or_else(|| font::missing(columns)),
+                    (Some(..), ..) => font::missing(columns),
+                };
+
+                if attr.is_bold() {
+                    bitmap.iter_mut().for_each(|row| *row |= *row << 1);
+                }
+                let line = (1 << columns) - 1;
+                if attr.is_strike_out() {
+                    bitmap[STRIKE_OUT] |= line;
+                }
+                if attr.is_overline() {
+                    bitmap[OVERLINE] |= line;
+                }
+
+                let (left, top) = (x as usize * CELL_WIDTH, y as usize * CELL_HEIGHT);
+                let underline = match (attr.is_underline(), attr.is_double_underline()) {
+                    (true, _) => underline(current.underline, left, columns),
+                    (false, true) => underline(Underline::Double, left, columns),
+                    (false, false) => Bitmap::default(),
+                };
+                let underline_color = current.underline_color.unwrap_or(fg);
+
+                for (dy, (row, line)) in bitmap.into_iter().zip(underline).enumerate() {
+                    let start = (top + dy) * width + left;
+                    for (dx, pixel) in pixels[start..start + columns].iter_mut().enumerate() {
+                        *pixel = match (line & (1 << dx) != 0, row & (1 << dx) != 0) {
+                            (true, _) => underline_color,
+                            (false, true) => fg,
+                            (false, false) => bg,
+                        };
+                    }
+                }
+            }
+        }
+
+        png::encode(width as u32, height as u32, &pixels)
+    }
+}
+
+/// Draw an underline across `columns` pixels, starting at the `left` pixel of the image
+///
+/// The patterns are based on the position in the image, so they continue across cells
+fn underline(style: Underline, left: usize, columns: usize) -> Bitmap {
+    let mut bitmap = Bitmap::default();
+    for dx in 0..columns {
+        let (x, bit) = (left + dx, 1 << dx);
+        match style {
+            Underline::Single => bitmap[UNDERLINE] |= bit,
+            // there is only room for two rows beneath the glyphs
+            Underline::Double => {
+                bitmap[UNDERLINE] |= bit;
+                bitmap[UNDERLINE + 1] |= bit;
+            }
+            Underline::Curly => bitmap[UNDERLINE + (x / 2) % 2] |= bit,
+            Underline::Dotted if x % 2 == 0 => bitmap[UNDERLINE] |= bit,
+            Underline::Dashed if x % CELL_WIDTH < 4 => bitmap[UNDERLINE] |= bit,
+            Underline::Dotted | Underline::Dashed => {}
+        }
+    }
+    bitmap
+}
@@ -0,0 +1,292 @@
+//! An embedded bitmap font, used to rasterize a [`Surface`](crate::Surface)
+//!
+//! - ASCII is drawn from hand drawn `5x9` glyphs
+//! - Box-drawing, block elements and braille patterns are generated, so they connect across cells
+
+/// The width of a cell, in pixels
+pub(super) const CELL_WIDTH: usize = 6;
+/// The height of a cell, in pixels
+pub(super) const CELL_HEIGHT: usize = 12;
+
+/// A rasterized cell, each row has a bit set for each pixel (the lsb is the left-most pixel)
+pub(super) type Bitmap = [u16; CELL_HEIGHT];
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 9;
+/// Where the glyphs start in a cell, this leaves room for an overline
+const GLYPH_TOP: usize = 1;
+
+/// The row of an overline, above the glyphs
+pub(super) const OVERLINE: usize = 0;
+/// The row of an underline, below any descenders
+pub(super) const UNDERLINE: usize = GLYPH_TOP + GLYPH_HEIGHT;
+/// The row of a strike-out, through the middle of the lowercase letters
+pub(super) const STRIKE_OUT: usize = GLYPH_TOP + 4;
+
+/// The glyphs for `' '..='\x7f'`, in bands of 16 glyphs
+///
+/// Each glyph is `5x9`, followed by a space. Row `6` is the baseline, rows `7` and `8` are for descenders
+const ASCII: &[u8; 96 * 6 * GLYPH_HEIGHT] = b"\
+    ..... ..#.. .#.#. .#.#. ..#.. ##... .##.. ..#.. ...#. .#... ..... ..... ..... ..... ..... ....# \
+    ..... ..#.. .#.#. .#.#. .#### ##..# #..#. ..#.. ..#.. ..#.. ..#.. ..#.. ..... ..... ..... ...#. \
+    ..... ..#.. ..... ##### #.#.. ...#. #.#.. .#... .#... ...#. #.#.# ..#.. ..... ..... ..... ...#. \
+    ..... ..#.. ..... .#.#. .###. ..#.. .#... ..... .#... ...#. .###. ##### ..... ##### ..... ..#.. \
+    ..... ..#.. ..... ##### ..#.# .#... #.#.# ..... .#... ...#. #.#.# ..#.. ..... ..... ..... .#... \
+    ..... ..... ..... .#.#. ####. #..## #..#. ..... ..#.. ..#.. ..#.. ..#.. ..#.. ..... .##.. .#... \
+    ..... ..#.. ..... .#.#. ..#.. ...## .##.# ..... ...#. .#... ..... ..... ..#.. ..... .##.. #.... \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... .#... ..... ..... ..... \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... \
+    .###. ..#.. .###. ##### ...#. ##### ..##. ##### .###. .###. ..... ..... ...#. ..... .#... .###. \
+    #...# .##.. #...# ...#. ..##. #.... .#... ....# #...# #...# .##.. .##.. ..#.. ..... ..#.. #...# \
+    #..## ..#.. ....# ..#.. .#.#. ####. #.... ...#. #...# #...# .##.. .##.. .#... ##### ...#. ....# \
+    #.#.# ..#.. ...#. ...#. #..#. ....# ####. ..#.. .###. .#### ..... ..... #.... ..... ....# ...#. \
+    ##..# ..#.. ..#.. ....# ##### ....# #...# .#... #...# ....# .##.. .##.. .#... ##### ...#. ..#.. \
+    #...# ..#.. .#... #...# ...#. #...# #...# .#... #...# ...#. .##.. .##.. ..#.. ..... ..#.. ..... \
+    .###. .###. ##### .###. ...#. .###. .###. .#... .###. .##.. ..... ..#.. ...#. ..... .#... ..#.. \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... .#... ..... ..... ..... ..... \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... \
+    .###. .###. ####. .###. ###.. ##### ##### .###. #...# .###. ..### #...# #.... #...# #...# .###. \
+    #...# #...# #...# #...# #..#. #.... #.... #...# #...# ..#.. ...#. #..#. #.... ##.## #...# #...# \
+    ....# #...# #...# #.... #...# #.... #.... #.... #...# ..#.. ...#. #.#.. #.... #.#.# ##..# #...# \
+    .##.# ##### ####. #.... #...# ####. ####. #.### ##### ..#.. ...#. ##... #.... #.#.# #.#.# #...# \
+    #.#.# #...# #...# #.... #...# #.... #.... #...# #...# ..#.. ...#. #.#.. #.... #...# #..## #...# \
+    #.#.# #...# #...# #...# #..#. #.... #.... #...# #...# ..#.. #..#. #..#. #.... #...# #...# #...# \
+    .###. #...# ####. .###. ###.. ##### #.... .#### #...# .###. .##.. #...# ##### #...# #...# .###. \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... \
+    ####. .###. ####. .#### ##### #...# #...# #...# #...# #...# ##### .###. #.... .###. ..#.. ..... \
+    #...# #...# #...# #.... ..#.. #...# #...# #...# #...# #...# ....# .#... .#... ...#. .#.#. ..... \
+    #...# #...# #...# #.... ..#.. #...# #...# #...# .#.#. .#.#. ...#. .#... .#... ...#. #...# ..... \
+    ####. #...# ####. .###. ..#.. #...# #...# #.#.# ..#.. ..#.. ..#.. .#... ..#.. ...#. ..... ..... \
+    #.... #.#.# #.#.. ....# ..#.. #...# #...# #.#.# .#.#. ..#.. .#... .#... ...#. ...#. ..... ..... \
+    #.... #..#. #..#. ....# ..#.. #...# .#.#. #.#.# #...# ..#.. #.... .#... ...#. ...#. ..... ..... \
+    #.... .##.# #...# ####. ..#.. .###. ..#.. .#.#. #...# ..#.. ##### .###. ....# .###. ..... ..... \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ##### \
+    ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... ..... \
+    .#... ..... #.... ..... ....# ..... ..##. ..... #.... ..#.. ...#. #.... .##.. ..... ..... ..... \
+    ..#.. ..... #.... ..... ....# ..... .#..# ..... #.... ..... ..... #.... ..#.. ..... ..... ..... \
+    ..... .###. ####. .###. .#### .###. .#... .#### #.##. .##.. ..##. #..#. ..#.. ##.#. #.##. .###. \
+    ..... ....# #...# #.... #...# #...# ###.. #...# ##..# ..#.. ...#. #.#.. ..#.. #.#.# ##..# #...# \
+    ..... .#### #...# #.... #...# ##### .#... #...# #...# ..#.. ...#. ##... ..#.. #.#.# #...# #...# \
+    ..... #...# #...# #...# #...# #.... .#... #...# #...# ..#.. ...#. #.#.. ..#.. #.#.# #...# #...# \
+    ..... .#### ####. .###. .#### .###. .#... .#### #...# .###. ...#. #..#. .###. #.#.# #...# .###. \
+    ..... ..... ..... ..... ..... ..... ..... ....# ..... ..... #..#. ..... ..... ..... ..... ..... \
+    ..... ..... ..... ..... ..... ..... ..... .###. ..... ..... .##.. ..... ..... ..... ..... ..... \
+    ..... ..... ..... ..... .#... ..... ..... ..... ..... ..... ..... ...#. ..#.. .#... ..... ..... \
+    ..... ..... ..... ..... .#... ..... ..... ..... ..... ..... ..... ..#.. ..#.. ..#.. ..... ..... \
+    ####. .#### #.##. .#### ###.. #...# #...# #...# #...# #...# ##### ..#.. ..#.. ..#.. .#... ..... \
+    #...# #...# ##..# #.... .#... #...# #...# #...# .#.#. #...# ...#. .#... ..#.. ...#. #.#.# ..... \
+    #...# #...# #.... .###. .#... #...# #...# #.#.# ..#.. #...# ..#.. ..#.. ..#.. ..#.. ...#. ..... \
+    #...# #...# #.... ....# .#..# #..## .#.#. #.#.# .#.#. #...# .#... ..#.. ..#.. ..#.. ..... ..... \
+    ####. .#### #.... ####. ..##. .##.# ..#.. .#.#. #...# .#### ##### ...#. ..#.. .#... ..... ..... \
+    #.... ....# ..... ..... ..... ..... ..... ..... ..... ....# ..... ..... ..... ..... ..... ..... \
+    #.... ....# ..... ..... ..... ..... ..... ..... ..... .###. ..... ..... ..... ..... ..... ..... \
+";
+
+/// The lines of each box-drawing character in `U+2500..=U+257F`, as `up right down left`
+///
+/// `l` is a light line, `h` is a heavy line, and `d` is a double line
+const BOX: &[u8; 128 * 5] = b"\
+    .l.l .h.h l.l. h.h. .l.l .h.h l.l. h.h. \
+    .l.l .h.h l.l. h.h. .ll. .hl. .lh. .hh. \
+    ..ll ..lh ..hl ..hh ll.. lh.. hl.. hh.. \
+    l..l l..h h..l h..h lll. lhl. hll. llh. \
+    hlh. hhl. lhh. hhh. l.ll l.lh h.ll l.hl \
+    h.hl h.lh l.hh h.hh .lll .llh .hll .hlh \
+    .lhl .lhh .hhl .hhh ll.l ll.h lh.l lh.h \
+    hl.l hl.h hh.l hh.h llll lllh lhll lhlh \
+    hlll llhl hlhl hllh hhll llhh lhhl hhlh \
+    lhhh hlhh hhhl hhhh .l.l .h.h l.l. h.h. \
+    .d.d d.d. .dl. .ld. .dd. ..ld ..dl ..dd \
+    ld.. dl.. dd.. l..d d..l d..d ldl. dld. \
+    ddd. l.ld d.dl d.dd .dld .ldl .ddd ld.d \
+    dl.l dd.d ldld dldl dddd .ll. ..ll l..l \
+    ll.. .... .... .... ...l l... .l.. ..l. \
+    ...h h... .h.. ..h. .h.l l.h. .l.h h.l. \
+";
+
+/// Get the [`Bitmap`] for this character, if the font has it
+pub(super) fn glyph(char: char) -> Option<Bitmap> {
+    match char {
+        ' '..='~' => Some(ascii(char as usize - ' ' as usize)),
+        '\u{2571}'..='\u{2573}' => Some(diagonal(char)),
+        '\u{2500}'..='\u{257F}' => Some(box_drawing(char as usize - 0x2500)),
+        '\u{2580}'..='\u{259F}' => Some(block(char)),
+        '\u{2800}'..='\u{28FF}' => Some(braille(char as u32 as u8)),
+        _ => None,
+    }
+}
+
+/// A hollow box, used for characters that the font doesn't have
+pub(super) fn missing(width: usize) -> Bitmap {
+    let mut bitmap = Bitmap::default();
+    fill(&mut bitmap, 0..width, 1..CELL_HEIGHT - 1);
+    fill_with(&mut bitmap, 1..width - 1, 2..CELL_HEIGHT - 2, false);
+    bitmap
+}
+
+fn ascii(index: usize) -> Bitmap {
+    let (band, column) = (index / 16, index % 16);
+    let mut bitmap = Bitmap::default();
+    for y in 0..GLYPH_HEIGHT {
+        let start = ((band * GLYPH_HEIGHT + y) * 16 + column) * (GLYPH_WIDTH + 1);
+        for (x, &pixel) in ASCII[start..start + GLYPH_WIDTH].iter().enumerate() {
+            if pixel == b'#' {
+                bitmap[GLYPH_TOP + y] |= 1 << x;
+            }
+        }
+    }
+    bitmap
+}
+
+fn box_drawing(index: usize) -> Bitmap {
+    // the lines meet at this pixel
+    const X: usize = 2;
+    const Y: usize = 5;
+
+    let mut bitmap = Bitmap::default();
+    let lines = &BOX[index * 5..index * 5 + 4];
+    for (direction, &weight) in lines.iter().enumerate() {
+        // the offsets of each stroke from the center, and how far up and left extend past the center
+        let (offsets, extend): (&[isize], usize) = match weight {
+            b'l' => (&[0], 0),
+            b'h' => (&[0, 1], 1),
+            b'd' => (&[-1, 1], 1),
+            _ => continue,
+        };
+
+        for &offset in offsets {
+            let (x, y) = (X.wrapping_add_signed(offset), Y.wrapping_add_signed(offset));
+            let (columns, rows) = match direction {
+                0 => (x..x + 1, 0..Y + 1 + extend),
+                1 => (X..CELL_WIDTH, y..y + 1),
+                2 => (x..x + 1, Y..CELL_HEIGHT),
+                _ => (0..X + 1 + extend, y..y + 1),
+            };
+            fill(&mut bitmap, columns, rows);
+        }
+    }
+    bitmap
+}
+
+fn diagonal(char: char) -> Bitmap {
+    let mut bitmap = Bitmap::default();
+    for y in 0..CELL_HEIGHT {
+        let x = y * CELL_WIDTH / CELL_HEIGHT;
+        if char != '\u{2572}' {
+            bitmap[CELL_HEIGHT - 1 - y] |= 1 << x;
+        }
+        if char != '\u{2571}' {
+            bitmap[y] |= 1 << x;
+        }
+    }
+    bitmap
+}
+
+fn block(char: char) -> Bitmap {
+    const W: usize = CELL_WIDTH;
+    const H: usize = CELL_HEIGHT;
+    // the number of pixels in `n` eighths of a cell
+    const fn eighths(n: usize, size: usize) -> usize {
+        (n * size + 4) / 8
+    }
+
+    let mut bitmap = Bitmap::default();
+    let mut quadrants = |quadrants: [bool; 4]| {
+        let [upper_left, upper_right, lower_left, lower_right] = quadrants;
+        let (x, y) = (W / 2, H / 2);
+        for (set, columns, rows) in [
+            (upper_left, 0..x, 0..y),
+            (upper_right, x..W, 0..y),
+            (lower_left, 0..x, y..H),
+            (lower_right, x..W, y..H),
+        ] {
+            if set {
+                fill(&mut bitmap, columns, rows);
+            }
+        }
+    };
+
+    match char {
+        '\u{2580}' => fill(&mut bitmap, 0..W, 0..H / 2),
+        '\u{2581}'..='\u{2588}' => {
+            let n = char as usize - 0x2580;
+            fill(&mut bitmap, 0..W, H - eighths(n, H)..H)
+        }
+        '\u{2589}'..='\u{258F}' => {
+            let n = 0x2590 - char as usize;
+            fill(&mut bitmap, 0..eighths(n, W), 0..H)
+        }
+        '\u{2590}' => fill(&mut bitmap, W / 2..W, 0..H),
+        '\u{2591}'..='\u{2593}' => {
+            for (y, row) in bitmap.iter_mut().enumerate() {
+                for x in 0..W {
+                    let set = match char {
+                        '\u{2591}' => x % 2 == 0 && y % 2 == 0,
+                        '\u{2592}' => (x + y) % 2 == 0,
+                        _ => x % 2 == 0 || y % 2 == 0,
+                    };
+                    if set {
+                        *row |= 1 << x;
+                    }
+                }
+            }
+        }
+        '\u{2594}' => fill(&mut bitmap, 0..W, 0..eighths(1, H)),
+        '\u{2595}' => fill(&mut bitmap, W - eighths(1, W)..W, 0..H),
+        '\u{2596}' => quadrants([false, false, true, false]),
+        '\u{2597}' => quadrants([false, false, false, true]),
+        '\u{2598}' => quadrants([true, false, false, false]),
+        '\u{2599}' => quadrants([true, false, true, true]),
+        '\u{259A}' => quadrants([true, false, false, true]),
+        '\u{259B}' => quadrants([true, true, true, false]),
+        '\u{259C}' => quadrants([true, true, false, true]),
+        '\u{259D}' => quadrants([false, true, false, false]),
+        '\u{259E}' => quadrants([false, true, true, false]),
+        '\u{259F}' => quadrants([false, true, true, true]),
+        _ => {}
+    }
+    bitmap
+}
+
+fn braille(dots: u8) -> Bitmap {
+    // the dot for each bit, as (column, row)
+    const DOTS: [(usize, usize); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+
+    let mut bitmap = Bitmap::default();
+    for (bit, (column, row)) in DOTS.into_iter().enumerate() {
+        if dots & (1 << bit) != 0 {
+            let (x, y) = (column * 3, row * 3);
+            fill(&mut bitmap, x..x + 2, y..y + 2);
+        }
+    }
+    bitmap
+}
+
+fn fill(bitmap: &mut Bitmap, columns: std::ops::Range<usize>, rows: std::ops::Range<usize>) {
+    fill_with(bitmap, columns, rows, true)
+}
+
+fn fill_with(
+    bitmap: &mut Bitmap,
+    columns: std::ops::Range<usize>,
+    rows: std::ops::Range<usize>,
+    set: bool,
+) {
+    let mask = columns.fold(0_u16, |mask, x| mask | (1 << x));
+    for row in &mut bitmap[rows] {
+        match set {
+            true => *row |= mask,
+            false => *row &= !mask,
+        }
+    }
+}
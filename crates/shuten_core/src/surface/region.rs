@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use super::{Cell, Surface};
+use crate::geom::{pos2, Pos2, Rect};
+
+/// Which neighbors of a cell are connected to it
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Connectivity {
+    /// Only the horizontal and vertical neighbors
+    #[default]
+    Four,
+    /// The horizontal, vertical and diagonal neighbors
+    Eight,
+}
+
+impl Connectivity {
+    fn neighbors(self) -> &'static [(i32, i32)] {
+        const NEIGHBORS: [(i32, i32); 8] = [
+            (-1, 0),
+            (1, 0),
+            (0, -1),
+            (0, 1),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+        ];
+        match self {
+            Self::Four => &NEIGHBORS[..4],
+            Self::Eight => &NEIGHBORS,
+        }
+    }
+}
+
+impl Surface {
+    /// Get the connected region of cells, starting at `start`, that match the predicate
+    ///
+    /// This is empty if `start` is outside of the surface or doesn't match
+    ///
+    /// The positions are in breadth-first order, starting with `start`
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, vec2};
+    /// use shuten_core::{Cell, Connectivity, Surface};
+    ///
+    /// let mut surface = Surface::new(vec2(4, 4));
+    /// let rect = surface.rect();
+    /// surface.canvas().stroke_rect(rect, Cell::new('#'));
+    ///
+    /// let inside = surface.region(pos2(1, 1), Connectivity::Four, |cell| cell.char == ' ');
+    /// assert_eq!(inside.len(), 4);
+    ///
+    /// let border = surface.region(pos2(0, 0), Connectivity::Four, |cell| cell.char == '#');
+    /// assert_eq!(border.len(), 12);
+    /// ```
+    pub fn region(
+        &self,
+        start: Pos2,
+        connectivity: Connectivity,
+        predicate: impl FnMut(&Cell) -> bool,
+    ) -> Vec<Pos2> {
+        self.region_in(self.rect(), start, connectivity, predicate)
+    }
+
+    /// Get the connected region of cells, starting at `start`, that match the predicate, without leaving `bounds`
+    #[cfg_attr(feature = "profiling", profiling::function)]
+    pub(crate) fn region_in(
+        &self,
+        bounds: Rect,
+        start: Pos2,
+        connectivity: Connectivity,
+        mut predicate: impl FnMut(&Cell) -> bool,
+    ) -> Vec<Pos2> {
+        let bounds = bounds.intersect(self.rect());
+        if !bounds.contains(start) || !predicate(&self[start]) {
+            return Vec::new();
+        }
+
+        // the region is also the queue, so it is in the order it was visited
+        let mut seen = HashSet::from([start]);
+        let mut region = vec![start];
+        let mut next = 0;
+        while let Some(&pos) = region.get(next) {
+            next += 1;
+            for &(dx, dy) in connectivity.neighbors() {
+                let (x, y) = (i32::from(pos.x) + dx, i32::from(pos.y) + dy);
+                let (Ok(x), Ok(y)) = (u16::try_from(x), u16::try_from(y)) else {
+                    continue;
+                };
+
+                let neighbor = pos2(x, y);
+                if !bounds.contains(neighbor)
+                    || seen.contains(&neighbor)
+                    || !predicate(&self[neighbor])
+                {
+                    continue;
+                }
+                seen.insert(neighbor);
+                region.push(neighbor);
+            }
+        }
+        region
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::vec2;
+
+    #[test]
+    fn region_is_breadth_first() {
+        let surface = Surface::new(vec2(3, 3));
+        let region = surface.region(pos2(1, 1), Connectivity::Four, |_| true);
+        assert_eq!(
+            region,
+            [
+                pos2(1, 1),
+                pos2(0, 1),
+                pos2(2, 1),
+                pos2(1, 0),
+                pos2(1, 2),
+                pos2(0, 0),
+                pos2(0, 2),
+                pos2(2, 0),
+                pos2(2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn region_stays_in_bounds() {
+        let mut surface = Surface::new(vec2(4, 4));
+        surface[pos2(1, 0)] = Cell::new('#');
+        surface[pos2(0, 1)] = Cell::new('#');
+
+        let bounds = Rect::from_min_size(pos2(0, 0), vec2(3, 3));
+        let region = surface.region_in(bounds, pos2(2, 2), Connectivity::Four, |cell| {
+            cell.char == ' '
+        });
+        assert_eq!(region.len(), 6);
+        assert!(region.iter().all(|&pos| bounds.contains(pos)));
+        assert!(!region.contains(&pos2(0, 0)));
+    }
+}
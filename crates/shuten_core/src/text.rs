@@ -0,0 +1,359 @@
+//! Text layout for drawing strings with [`Canvas::text`](crate::Canvas::text)
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use crate::{
+    geom::{vec2, Align2, Vec2},
+    style::{Attribute, Color},
+    surface::grapheme_width,
+    Cell, Hyperlink,
+};
+
+/// How text should wrap when it reaches the edge of its [`Rect`](crate::geom::Rect)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    /// Don't wrap, lines are cut off at the edge
+    #[default]
+    None,
+    /// Wrap at any character
+    Char,
+    /// Wrap at word boundaries
+    ///
+    /// Words that are longer than a line are wrapped at any character
+    Word,
+}
+
+/// Text that can be drawn with [`Canvas::text`](crate::Canvas::text)
+///
+/// ```rust
+/// use shuten_core::geom::{rect, vec2, Align2};
+/// use shuten_core::text::{Text, Wrap};
+/// use shuten_core::Context;
+///
+/// let mut context = Context::new(rect(vec2(20, 5)));
+/// let mut canvas = context.canvas();
+///
+/// let text = Text::new("hello world, this is some text")
+///     .fg(0xFF0000)
+///     .align(Align2::CENTER_CENTER)
+///     .wrap(Wrap::Word)
+///     .ellipsis(true);
+///
+/// let used = canvas.text(canvas.area(), text);
+/// assert_eq!(used.height(), 2);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Text<'a> {
+    text: &'a str,
+    pub(crate) style: Cell,
+    pub(crate) align: Align2,
+    pub(crate) link: Option<Hyperlink<'a>>,
+    wrap: Wrap,
+    ellipsis: bool,
+}
+
+impl<'a> Text<'a> {
+    /// Create new text with the default style, left-top aligned and without wrapping
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            style: Cell::new(' '),
+            align: Align2::LEFT_TOP,
+            link: None,
+            wrap: Wrap::None,
+            ellipsis: false,
+        }
+    }
+
+    /// Set the colors and attribute of the text from this [`Cell`]
+    ///
+    /// The character of the [`Cell`] is ignored
+    pub const fn style(mut self, style: Cell) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the foreground of the text
+    pub fn fg(mut self, fg: impl Into<Color>) -> Self {
+        self.style = self.style.fg(fg);
+        self
+    }
+
+    /// Set the background of the text
+    pub fn bg(mut self, bg: impl Into<Color>) -> Self {
+        self.style = self.style.bg(bg);
+        self
+    }
+
+    /// Set the attribute of the text
+    ///
+    /// If `none` is provided, then the attribute is reset
+    pub fn attr(mut self, attr: impl Into<Option<Attribute>>) -> Self {
+        self.style = self.style.attr(attr);
+        self
+    }
+
+    /// Make the text a hyperlink to this `uri`
+    ///
+    /// Text with the same `id` and `uri` is treated as a single link by the terminal, even if it is wrapped
+    ///
+    /// ```rust
+    /// use shuten_core::geom::{pos2, rect, vec2};
+    /// use shuten_core::text::Text;
+    /// use shuten_core::Context;
+    ///
+    /// let mut context = Context::new(rect(vec2(20, 1)));
+    /// let mut canvas = context.canvas();
+    /// canvas.text(canvas.area(), Text::new("docs").link("https://docs.rs", "docs"));
+    ///
+    /// let surface = context.surface();
+    /// let target = surface.link(&surface[pos2(3, 0)]).unwrap();
+    /// assert_eq!(target.id, Some("docs"));
+    /// ```
+    pub fn link(mut self, uri: &'a str, id: impl Into<Option<&'a str>>) -> Self {
+        self.link = Some(Hyperlink { uri, id: id.into() });
+        self
+    }
+
+    /// How the text should be aligned in its [`Rect`](crate::geom::Rect)
+    pub const fn align(mut self, align: Align2) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// How the text should be wrapped
+    pub const fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Should truncated text end with an ellipsis (`…`)?
+    pub const fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Measure the size of this text when it is constrained to `max`
+    ///
+    /// This can be used to allocate a [`Rect`](crate::geom::Rect) with a [`Linear`](crate::layout::Linear) layout
+    pub fn size(&self, max: Vec2) -> Vec2 {
+        let lines = self.layout(max);
+        let width = lines.iter().map(Line::width).max().unwrap_or(0);
+        vec2(width, lines.len() as u16)
+    }
+
+    pub(crate) fn layout(&self, max: Vec2) -> Vec<Line<'a>> {
+        let mut lines = Vec::new();
+        if max.x == 0 || max.y == 0 {
+            return lines;
+        }
+
+        for paragraph in self.text.lines() {
+            let start = lines.len();
+            match self.wrap {
+                Wrap::None => lines.push(graphemes(paragraph).collect()),
+                Wrap::Char => wrap_chars(paragraph, max.x, &mut lines),
+                Wrap::Word => wrap_words(paragraph, max.x, &mut lines),
+            }
+            if lines.len() == start {
+                lines.push(Line::default())
+            }
+            if lines.len() > max.y as usize {
+                break;
+            }
+        }
+
+        let truncated = lines.len() > max.y as usize;
+        lines.truncate(max.y as usize);
+
+        for line in &mut lines {
+            if line.overflows(0, max.x) {
+                line.truncate(max.x);
+                if self.ellipsis {
+                    line.ellipsize(max.x)
+                }
+            }
+        }
+
+        if truncated && self.ellipsis {
+            if let Some(line) = lines.last_mut() {
+                line.ellipsize(max.x)
+            }
+        }
+
+        lines
+    }
+}
+
+impl<'a> From<&'a str> for Text<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<'a> From<&'a String> for Text<'a> {
+    fn from(value: &'a String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A laid out line of graphemes
+#[derive(Default, Debug)]
+pub(crate) struct Line<'a> {
+    pub(crate) graphemes: Vec<(&'a str, u16)>,
+    // a line can be wider than `u16::MAX` before it is truncated
+    width: usize,
+}
+
+impl<'a> Line<'a> {
+    /// The width of this line, this fits once the line has been laid out
+    pub(crate) fn width(&self) -> u16 {
+        u16::try_from(self.width).unwrap_or(u16::MAX)
+    }
+
+    fn push(&mut self, grapheme: &'a str, width: u16) {
+        self.graphemes.push((grapheme, width));
+        self.width += usize::from(width);
+    }
+
+    /// Would this line be wider than `max` with `width` more columns?
+    fn overflows(&self, width: usize, max: u16) -> bool {
+        self.width + width > usize::from(max)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.graphemes.is_empty()
+    }
+
+    fn truncate(&mut self, max: u16) {
+        while self.overflows(0, max) {
+            let Some((_, width)) = self.graphemes.pop() else {
+                break;
+            };
+            self.width -= usize::from(width);
+        }
+    }
+
+    fn trim_end(&mut self) {
+        while let Some(&(" ", width)) = self.graphemes.last() {
+            self.graphemes.pop();
+            self.width -= usize::from(width);
+        }
+    }
+
+    fn ellipsize(&mut self, max: u16) {
+        const ELLIPSIS: &str = "…";
+        self.truncate(max.saturating_sub(1));
+        self.push(ELLIPSIS, 1);
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u16)> for Line<'a> {
+    fn from_iter<T: IntoIterator<Item = (&'a str, u16)>>(iter: T) -> Self {
+        let mut line = Self::default();
+        for (grapheme, width) in iter {
+            line.push(grapheme, width)
+        }
+        line
+    }
+}
+
+impl<'a> Extend<(&'a str, u16)> for Line<'a> {
+    fn extend<T: IntoIterator<Item = (&'a str, u16)>>(&mut self, iter: T) {
+        for (grapheme, width) in iter {
+            self.push(grapheme, width)
+        }
+    }
+}
+
+fn graphemes(input: &str) -> impl Iterator<Item = (&str, u16)> {
+    input.graphemes(true).map(|grapheme| match grapheme {
+        "\t" => (" ", 1),
+        grapheme => (grapheme, grapheme_width(grapheme)),
+    })
+}
+
+fn wrap_chars<'a>(paragraph: &'a str, max: u16, lines: &mut Vec<Line<'a>>) {
+    let mut line = Line::default();
+    for (grapheme, width) in graphemes(paragraph) {
+        if line.overflows(usize::from(width), max) && !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        line.push(grapheme, width);
+    }
+    if !line.is_empty() {
+        lines.push(line)
+    }
+}
+
+fn wrap_words<'a>(paragraph: &'a str, max: u16, lines: &mut Vec<Line<'a>>) {
+    let start = lines.len();
+    let mut line = Line::default();
+
+    for word in paragraph.split_word_bounds() {
+        let width = graphemes(word).map(|(_, width)| usize::from(width)).sum();
+
+        if word.chars().all(char::is_whitespace) {
+            // wrapped lines don't start with whitespace
+            if line.is_empty() && lines.len() > start {
+                continue;
+            }
+            if line.overflows(width, max) {
+                line.trim_end();
+                lines.push(std::mem::take(&mut line));
+                continue;
+            }
+            line.extend(graphemes(word));
+            continue;
+        }
+
+        if line.overflows(width, max) && !line.is_empty() {
+            line.trim_end();
+            lines.push(std::mem::take(&mut line));
+        }
+
+        for (grapheme, width) in graphemes(word) {
+            if line.overflows(usize::from(width), max) && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(grapheme, width);
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geom::{pos2, rect},
+        Context,
+    };
+
+    #[test]
+    fn lines_wider_than_u16() {
+        let long = "a".repeat(70_000);
+        let max = vec2(u16::MAX, 2);
+
+        assert_eq!(Text::new(&long).size(max), vec2(u16::MAX, 1));
+        assert_eq!(
+            Text::new(&long).wrap(Wrap::Char).size(max),
+            vec2(u16::MAX, 2)
+        );
+        assert_eq!(
+            Text::new(&long).wrap(Wrap::Word).size(max),
+            vec2(u16::MAX, 2)
+        );
+
+        let words = "word ".repeat(14_000);
+        assert_eq!(Text::new(&words).wrap(Wrap::Word).size(max).y, 2);
+
+        let mut context = Context::new(rect(vec2(10, 1)));
+        let mut canvas = context.canvas();
+        let used = canvas.text(canvas.area(), Text::new(&long).ellipsis(true));
+        assert_eq!(used.width(), 10);
+        assert_eq!(context.surface()[pos2(9, 0)].char, '…');
+    }
+}